@@ -1,15 +1,128 @@
 use std::{
-    cmp::Reverse,
+    cmp::{Ordering, Reverse},
     collections::{BinaryHeap, HashMap},
 };
 
 use algorithm_derive::Algorithm;
 
-use crate::core::{Algorithm, AlgorithmCommon, Dir, Order, Problem, node::Node};
+use crate::core::{
+    Algorithm, AlgorithmCommon, ConfigError, PackedBoard, Problem, SearchConfig,
+    limits::Budget,
+    node::Node,
+    tree::{NodeId, SearchTree},
+};
+use crate::heuristics::Slide;
+
+/// How the heuristic estimate is combined with the path cost.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Weighting {
+    /// f = g + h
+    #[default]
+    Standard,
+    /// f = g + w·h, solution cost is at most w times the optimum
+    Weighted(f64),
+    /// f = h, no bound on solution cost
+    Greedy,
+}
+
+impl Weighting {
+    fn cost(&self, depth: u16, estimate: usize) -> Cost {
+        match self {
+            Weighting::Standard => Cost((depth as usize + estimate) as f64),
+            Weighting::Weighted(w) => Cost(depth as f64 + w * estimate as f64),
+            Weighting::Greedy => Cost(estimate as f64),
+        }
+    }
+
+    /// Factor by which the solution may exceed the optimal cost,
+    /// assuming an admissible heuristic.
+    pub fn bound(&self) -> Option<f64> {
+        match self {
+            Weighting::Standard => Some(1.0),
+            Weighting::Weighted(w) => Some(w.max(1.0)),
+            Weighting::Greedy => None,
+        }
+    }
+}
+
+// Priority of a node in the open set
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Cost(f64);
+
+impl Eq for Cost {}
+
+impl Ord for Cost {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl PartialOrd for Cost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Entry of the open set, nodes of equal cost are expanded in insertion order,
+// so the requested move order decides between them
+struct Open {
+    cost: Cost,
+    seq: usize,
+    // Only the key, blank and tree index are kept, the node is rebuilt when expanded
+    key: PackedBoard,
+    blank: u16,
+    id: NodeId,
+    // Heuristic value of the node, children are estimated from it
+    estimate: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Open {}
 
-#[derive(Default, Algorithm)]
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.cost, self.seq).cmp(&(other.cost, other.seq))
+    }
+}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default, Clone, Algorithm)]
 pub struct AStar {
     common: AlgorithmCommon,
+    weighting: Weighting,
+}
+
+impl AStar {
+    pub fn with_weighting(weighting: Weighting) -> Self {
+        Self {
+            weighting,
+            ..Self::default()
+        }
+    }
+
+    /// Weighted A* expanding nodes in order of g + w·h.
+    pub fn weighted(weight: f64) -> Self {
+        Self::with_weighting(Weighting::Weighted(weight))
+    }
+
+    /// Greedy best-first search expanding nodes in order of h alone.
+    pub fn greedy() -> Self {
+        Self::with_weighting(Weighting::Greedy)
+    }
+
+    pub fn weighting(&self) -> Weighting {
+        self.weighting
+    }
 }
 
 impl Algorithm for AStar {
@@ -17,77 +130,154 @@ impl Algorithm for AStar {
     ///
     /// # Arguments
     /// * `problem` - The initial puzzle state to solve
-    /// * `order` - The move ordering and heuristic to use (e.g., Manhattan distance)
+    /// * `config` - The heuristic (e.g., Manhattan distance), move ordering and search limits to use
     ///
     /// # Returns
     /// * `Some(Node)` containing the solved state if a solution is found
-    /// * `None` if no solution exists or if the search space is exhausted
+    /// * `None` if no solution exists, a limit is reached or `config` has no heuristic
     ///
     /// # Behavior
     /// 1.Initializes priority queue (open set) and visited states hash map
     /// 2.Uses the specified heuristic function to guide the search
-    /// 3.Expands nodes in order of (heuristic + path cost)
+    /// 3.Expands nodes in order of the cost given by `weighting`, ties in the move order
     /// 4.Tracks visited states to avoid cycles
-    /// 5.Updates algorithm statistics (visited count, depth reached, etc.)
+    /// 5.Stops once a depth, node, time or memory limit is exceeded
+    /// 6.Updates algorithm statistics (visited count, depth reached, etc.)
     ///
     /// # Performance Characteristics
     /// * Time complexity: O(b^d) where b is branching factor, d is solution depth
     /// * Space complexity: O(b^d) for storing visited states
     /// * Complete: Will find solution if one exists
-    /// * Optimal: Finds shortest path when using admissible heuristic
+    /// * Optimal: Finds shortest path when using admissible heuristic and
+    ///   `Weighting::Standard`, within `Weighting::bound` of it otherwise
     ///
-    fn run(&mut self, problem: Problem, order: &Order) -> Option<Node> {
+    fn run(&mut self, problem: Problem, config: &SearchConfig) -> Option<Node> {
+        let Some(heuristic) = config.heuristic() else {
+            self.set_reached_depth(-1);
+            return None;
+        };
+
         let mut open = BinaryHeap::new();
         let mut visited = HashMap::new();
-        let mut dirs = Dir::values();
-        dirs.reverse();
+        let dirs = config.order();
 
-        let heuristic_fn = order.get_heuristic();
+        let weighting = self.weighting;
+        self.suboptimality_bound = weighting.bound();
 
-        let state = Node::new(problem.get());
-        visited.insert(state.hash_code(), 0);
+        let mut state = Node::new(problem.get()).expect("Board is invalid");
+        let goal = problem.goal();
+        let mut budget = Budget::new(config, &state);
+        let mut tree = SearchTree::new(&mut state);
+        visited.insert(state.key().clone(), 0);
         let mut count = 0;
+        let mut seq = 0;
 
-        open.push(Reverse((heuristic_fn(state.get_board(), 0), state)));
+        let estimate = heuristic.estimate(&state.get_board(), &goal);
+        let shape = state.shape();
+        open.push(Reverse(Open {
+            cost: weighting.cost(0, estimate),
+            seq,
+            key: state.key().clone(),
+            blank: state.blank(),
+            id: state.id(),
+            estimate,
+        }));
 
-        while let Some(Reverse((_, current))) = open.pop() {
+        while let Some(Reverse(Open {
+            key,
+            blank,
+            id,
+            estimate: current_estimate,
+            ..
+        })) = open.pop()
+        {
             count += 1;
 
-            if current.depth() > *visited.get(&current.hash_code()).unwrap_or(&u8::MAX) {
+            if tree.depth(id) > *visited.get(&key).unwrap_or(&u16::MAX) {
                 continue;
             }
+            let current = Node::restore(key, blank, shape, id, &tree);
 
-            for child in current.get_child_nodes(dirs) {
-                if child.is_solved() {
-                    self.set_visited_count(visited.len());
-                    self.set_reached_depth(child.depth() as i16);
-                    self.set_result_len(child.depth());
-                    self.set_processed_count(count);
-                    return Some(child.clone());
-                }
+            if !budget.expand(visited.len()) {
+                break;
+            }
+
+            // Goal is tested on expansion, as the cost bound only holds for expanded nodes
+            if current.is_solved(&goal) {
+                self.set_visited_count(visited.len());
+                self.set_reached_depth(current.depth() as i32);
+                self.set_path(tree.path(current.id()));
+                self.set_processed_count(count);
+                return Some(current);
+            }
+
+            if !budget.within_depth(current.depth()) {
+                continue;
+            }
 
+            let tiles = current.get_board();
+            for mut child in current.get_child_nodes(dirs) {
                 let depth = child.depth();
-                let child_hash = child.hash_code();
+                let child_key = child.key().clone();
 
-                if depth < *visited.get(&child_hash).unwrap_or(&u8::MAX) {
-                    visited.insert(child_hash, depth);
+                if depth < *visited.get(&child_key).unwrap_or(&u16::MAX) {
+                    visited.insert(child_key, depth);
+                    tree.add(&current, &mut child);
 
-                    open.push(Reverse((heuristic_fn(child.get_board(), depth), child)));
+                    let slide =
+                        Slide::new(&tiles, current.blank() as usize, child.blank() as usize);
+                    let estimate = heuristic
+                        .update(&tiles, &goal, current_estimate, slide)
+                        .unwrap_or_else(|| heuristic.estimate(&child.get_board(), &goal));
+                    seq += 1;
+                    open.push(Reverse(Open {
+                        cost: weighting.cost(depth, estimate),
+                        seq,
+                        key: child.key().clone(),
+                        blank: child.blank(),
+                        id: child.id(),
+                        estimate,
+                    }));
                 }
             }
         }
 
+        self.set_visited_count(visited.len());
+        self.set_processed_count(count);
         self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
         None
     }
+
+    fn is_informed(&self) -> bool {
+        true
+    }
+
+    fn validate(&self, config: &SearchConfig) -> Result<(), ConfigError> {
+        if let Weighting::Weighted(w) = self.weighting
+            && !(w.is_finite() && w > 0.0)
+        {
+            return Err(ConfigError::InvalidWeight(w));
+        }
+
+        match config.heuristic() {
+            Some(_) => Ok(()),
+            None => Err(ConfigError::MissingHeuristic),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::core::{Algorithm, Order, Problem};
+    use std::sync::Arc;
+
+    use crate::core::{
+        Algorithm, ConfigError, Dir, Problem, SearchConfig, Tile, solver::AStarSolver,
+    };
+    use crate::heuristics::{LinearConflict, Manhattan, MaxOf};
 
-    use super::AStar;
+    use super::{AStar, Weighting};
     // Helper function to set up a solved state
     fn solved_state() -> Problem {
         Problem::new(vec![
@@ -115,16 +305,16 @@ mod tests {
         let mut astar = AStar::new();
 
         // Since the initial state is solved, Astar should return the solved state immediately
-        let result = astar.run(solved_state.clone(), &Order::Manh);
+        let result = astar.run(solved_state.clone(), &SearchConfig::informed(Manhattan));
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -135,16 +325,16 @@ mod tests {
         let mut astar = AStar::new();
 
         // Astar should be able to find the solved state
-        let result = astar.run(unsolved_state.clone(), &Order::Manh);
+        let result = astar.run(unsolved_state.clone(), &SearchConfig::informed(Manhattan));
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state()
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
     //
@@ -164,11 +354,15 @@ mod tests {
         let mut astar = AStar::new();
 
         // Astar should return the solved state for the 2x2 puzzle
-        let result = astar.run(unsolved_state.clone(), &Order::Manh);
+        let result = astar.run(unsolved_state.clone(), &SearchConfig::informed(Manhattan));
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
-            solved_state.iter().flatten().copied().collect::<Vec<u8>>()
+            result.unwrap().get_board(),
+            solved_state
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -176,8 +370,116 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_astar_empty_board() {
-        let empty_board: Vec<Vec<u8>> = Vec::new();
+        let empty_board: Vec<Vec<Tile>> = Vec::new();
         let mut astar = AStar::new(); // This should panic because the state is empty
-        astar.run(Problem::new(empty_board), &Order::Manh);
+        astar.run(
+            Problem::new(empty_board),
+            &SearchConfig::informed(Manhattan),
+        );
+    }
+
+    // Helper function to set up a state a few moves away from the solution
+    fn scrambled_state() -> Problem {
+        Problem::new(vec![
+            vec![5, 1, 3, 4],
+            vec![2, 0, 7, 8],
+            vec![9, 6, 10, 11],
+            vec![13, 14, 15, 12],
+        ])
+    }
+
+    // Test if weighted A* stays within its bound of the optimal solution
+    #[test]
+    fn test_weighted_astar_bound() {
+        let mut astar = AStar::new();
+        let mut weighted = AStar::weighted(2.0);
+
+        let optimal = astar
+            .run(scrambled_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+        let result = weighted
+            .run(scrambled_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+
+        assert_eq!(weighted.suboptimality_bound, Some(2.0));
+        assert!(result.is_solved(&scrambled_state().goal()));
+        assert!(result.depth() as f64 <= 2.0 * optimal.depth() as f64);
+    }
+
+    // Test if greedy best-first search finds a solution and reports no bound
+    #[test]
+    fn test_greedy_solving_puzzle() {
+        let mut greedy = AStar::greedy();
+
+        let result = greedy.run(scrambled_state(), &SearchConfig::informed(Manhattan));
+        assert!(result.is_some_and(|node| node.is_solved(&scrambled_state().goal())));
+        assert_eq!(greedy.weighting(), Weighting::Greedy);
+        assert_eq!(greedy.suboptimality_bound, None);
+    }
+
+    // Test if plain A* guarantees an optimal solution
+    #[test]
+    fn test_astar_reports_optimal_bound() {
+        let mut astar = AStar::new();
+
+        astar
+            .run(unsolved_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+        assert_eq!(astar.suboptimality_bound, Some(1.0));
+        assert_eq!(astar.get_result_len(), 1);
+    }
+
+    // Test if A* accepts a combination of heuristics
+    #[test]
+    fn test_astar_custom_heuristic() {
+        let mut astar = AStar::new();
+        let mut reference = AStar::new();
+        let heuristic = MaxOf::new(vec![Arc::new(Manhattan), Arc::new(LinearConflict)]);
+
+        let result = astar
+            .run(scrambled_state(), &SearchConfig::informed(heuristic))
+            .unwrap();
+        let optimal = reference
+            .run(scrambled_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+
+        assert!(result.is_solved(&scrambled_state().goal()));
+        assert_eq!(result.depth(), optimal.depth());
+    }
+
+    // Test if ties between equally good paths are broken by the move order
+    #[test]
+    fn test_astar_tie_breaking_follows_order() {
+        // Both DRULDR and RDLURD are optimal
+        let state = Problem::new(vec![vec![1, 2, 3], vec![4, 0, 8], vec![7, 6, 5]]);
+        let down_first = SearchConfig::builder()
+            .order([Dir::Down, Dir::Right, Dir::Up, Dir::Left])
+            .heuristic(Manhattan)
+            .build()
+            .unwrap();
+        let right_first = SearchConfig::builder()
+            .order([Dir::Right, Dir::Left, Dir::Down, Dir::Up])
+            .heuristic(Manhattan)
+            .build()
+            .unwrap();
+
+        let mut down = AStar::new();
+        let mut right = AStar::new();
+        let down_result = down.run(state.clone(), &down_first).unwrap();
+        let right_result = right.run(state, &right_first).unwrap();
+
+        assert_eq!(down_result.depth(), right_result.depth());
+        assert_eq!(down.get_path().first(), Some(&Dir::Down));
+        assert_eq!(right.get_path().first(), Some(&Dir::Right));
+    }
+
+    // Test if a solver rejects A* without a heuristic or with a bad weight
+    #[test]
+    fn test_astar_invalid_config() {
+        let result = AStarSolver::new(SearchConfig::default());
+        assert_eq!(result.err(), Some(ConfigError::MissingHeuristic));
+
+        let result = AStarSolver::weighted(SearchConfig::informed(Manhattan), -1.0);
+        assert_eq!(result.err(), Some(ConfigError::InvalidWeight(-1.0)));
     }
 }
@@ -2,11 +2,12 @@ use std::collections::{HashSet, VecDeque};
 
 use algorithm_derive::Algorithm;
 
-use crate::core::{Algorithm, Order, Problem, algorithm::AlgorithmCommon, node::Node};
+use crate::core::{
+    Algorithm, PackedBoard, Problem, SearchConfig, algorithm::AlgorithmCommon, limits::Budget,
+    node::Node, tree::SearchTree,
+};
 
-use super::MAX_DEPTH;
-
-#[derive(Default, Algorithm)]
+#[derive(Default, Clone, Algorithm)]
 pub struct Bfs {
     pub common: AlgorithmCommon,
 }
@@ -16,11 +17,11 @@ impl Algorithm for Bfs {
     ///
     /// # Arguments
     /// * `state` - The initial puzzle state to solve
-    /// * `order` - The move ordering to use (must be `Order::Perm` variant)
+    /// * `config` - The move ordering and search limits to use
     ///
     /// # Returns
-    /// * `Some(Node)` containing the solved state if found within `MAX_DEPTH`
-    /// * `None` if no solution exists or maximum depth is reached
+    /// * `Some(Node)` containing the solved state if found within the limits
+    /// * `None` if no solution exists or a limit is reached, see `get_stopped_by`
     ///
     /// # Behavior
     /// 1. Initializes visited set and queue with starting state
@@ -28,68 +29,79 @@ impl Algorithm for Bfs {
     /// 3. Expands nodes according to specified move ordering
     /// 4. Tracks visited states to avoid cycles
     /// 5. Updates algorithm statistics upon solution
-    /// 6. Abandons paths exceeding `MAX_DEPTH`
-    ///
-    /// # Panics
-    /// Will panic if `order` is not the `Order::Perm` variant
+    /// 6. Abandons paths exceeding the depth limit, if one is configured
+    /// 7. Stops once the node, time or memory limit is exceeded
     ///
     /// # Performance
     /// * Time: O(b^d) where b is branching factor, d is solution depth
     /// * Space: O(b^d) for storing visited states
-    /// * Complete: Will find solution if one exists within depth limit
+    /// * Complete: Will find solution if one exists within the depth limit, if any
     /// * Optimal: Finds shortest path solution
     ///
-    fn run(&mut self, state: Problem, order: &Order) -> Option<Node> {
-        let mut visited = HashSet::<u64>::new();
+    fn run(&mut self, state: Problem, config: &SearchConfig) -> Option<Node> {
+        let mut visited = HashSet::<PackedBoard>::new();
         let mut queue = VecDeque::new();
-        let current: Node = Node::new(state.get().clone());
+        let mut current: Node = Node::new(state.get().clone()).expect("Board is invalid");
+        let goal = state.goal();
 
-        if current.is_solved() {
+        if current.is_solved(&goal) {
             return Some(current);
         }
 
-        let dir_order = match order {
-            Order::Perm(dirs) => dirs,
-            _ => panic!("Podano nieprawidłowy typ Order!"),
-        };
+        let dir_order = config.order();
+        let mut budget = Budget::new(config, &current);
+        let mut tree = SearchTree::new(&mut current);
+        let shape = current.shape();
 
-        queue.push_back(current.clone());
-        visited.insert(current.hash_code());
+        // Only keys and blanks are queued, nodes are rebuilt from them and the tree
+        queue.push_back((current.key().clone(), current.blank(), current.id()));
+        visited.insert(current.key().clone());
         let mut count = 0;
 
-        while let Some(current) = queue.pop_front() {
+        while let Some((key, blank, id)) = queue.pop_front() {
+            let current = Node::restore(key, blank, shape, id, &tree);
+            if !budget.expand(visited.len()) {
+                break;
+            }
             count += 1;
 
-            if current.depth() >= MAX_DEPTH {
+            if !budget.within_depth(current.depth()) {
                 continue;
             }
 
-            for child in current.get_child_nodes(*dir_order) {
-                if visited.contains(&child.hash_code()) {
+            for mut child in current.get_child_nodes(dir_order) {
+                if visited.contains(child.key()) {
                     continue;
                 }
 
-                if child.is_solved() {
+                tree.add(&current, &mut child);
+
+                if child.is_solved(&goal) {
                     self.set_visited_count(visited.len());
-                    self.set_reached_depth(child.depth() as i16);
-                    self.set_result_len(child.depth());
+                    self.set_reached_depth(child.depth() as i32);
+                    self.set_path(tree.path(child.id()));
                     self.set_processed_count(count);
                     return Some(child);
                 }
 
-                visited.insert(child.hash_code());
-                queue.push_back(child);
+                visited.insert(child.key().clone());
+                queue.push_back((child.key().clone(), child.blank(), child.id()));
             }
         }
+
+        self.set_visited_count(visited.len());
+        self.set_processed_count(count);
         self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
         None
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::core::order::Order;
-    use crate::core::{Dir, Problem};
+    use crate::core::Problem;
+    use crate::core::SearchConfig;
+    use crate::core::Tile;
 
     use super::Algorithm;
     use super::Bfs;
@@ -120,16 +132,16 @@ mod tests {
         let mut bfs = Bfs::new();
 
         // Since the initial state is solved, BFS should return the solved state immediately
-        let result = bfs.run(solved_state.clone(), &Order::Perm(Dir::values()));
+        let result = bfs.run(solved_state.clone(), &SearchConfig::default());
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -140,16 +152,16 @@ mod tests {
         let mut bfs = Bfs::new();
 
         // BFS should be able to find the solved state
-        let result = bfs.run(unsolved_state.clone(), &Order::Perm(Dir::values()));
+        let result = bfs.run(unsolved_state.clone(), &SearchConfig::default());
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state()
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -169,25 +181,36 @@ mod tests {
         let mut bfs = Bfs::new();
 
         // BFS should return the solved state for the 2x2 puzzle
-        let result = bfs.run(unsolved_state, &Order::Perm(Dir::values()));
+        let result = bfs.run(unsolved_state, &SearchConfig::default());
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
+    // Test if BFS without a depth limit solves a board deeper than 20 moves
+    #[test]
+    fn test_bfs_unbounded_depth() {
+        // One of the two hardest 3x3 boards, 31 moves from the goal
+        let deep_state = Problem::new(vec![vec![8, 6, 7], vec![2, 5, 4], vec![3, 0, 1]]);
+        let mut bfs = Bfs::new();
+
+        let result = bfs.run(deep_state, &SearchConfig::default());
+        assert_eq!(result.unwrap().depth(), 31);
+    }
+
     // Test BFS on an empty board (should panic based on your implementation)
     #[test]
     #[should_panic]
     fn test_bfs_empty_board() {
-        let empty_board: Vec<Vec<u8>> = Vec::new();
+        let empty_board: Vec<Vec<Tile>> = Vec::new();
         let mut bfs = Bfs::new(); // This should panic because the state is empty
-        bfs.run(Problem::new(empty_board), &Order::Perm(Dir::values()));
+        bfs.run(Problem::new(empty_board), &SearchConfig::default());
     }
 }
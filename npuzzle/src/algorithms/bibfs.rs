@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use algorithm_derive::Algorithm;
+
+use crate::core::{
+    Algorithm, Dir, PackedBoard, Problem, SearchConfig, Shape, SideStats, Sides,
+    algorithm::AlgorithmCommon,
+    limits::Budget,
+    node::Node,
+    tree::{NodeId, SearchTree},
+};
+
+#[derive(Default, Clone, Algorithm)]
+pub struct BiBfs {
+    pub common: AlgorithmCommon,
+}
+
+// One direction of the search: every state reached so far and the last complete layer
+struct Side {
+    tree: SearchTree,
+    visited: HashMap<PackedBoard, NodeId>,
+    // Keys and blanks of the last layer, nodes are rebuilt from them and the tree
+    frontier: Vec<(PackedBoard, u16, NodeId)>,
+    shape: Shape,
+    depth: u16,
+    stats: SideStats,
+}
+
+impl Side {
+    fn new(mut root: Node) -> Self {
+        let tree = SearchTree::new(&mut root);
+        let mut visited = HashMap::new();
+        visited.insert(root.key().clone(), root.id());
+
+        Self {
+            tree,
+            visited,
+            frontier: vec![(root.key().clone(), root.blank(), root.id())],
+            shape: root.shape(),
+            depth: 0,
+            stats: SideStats::default(),
+        }
+    }
+
+    /// Expands the whole frontier by one layer and returns the cheapest meeting
+    /// with `other` as a (this side, other side) pair of tree indices.
+    fn expand(
+        &mut self,
+        other: &Side,
+        dirs: [Dir; 4],
+        budget: &mut Budget,
+    ) -> Option<(NodeId, NodeId)> {
+        let mut next = Vec::new();
+        let mut best: Option<(NodeId, NodeId)> = None;
+
+        for (key, blank, id) in self.frontier.drain(..) {
+            let current = Node::restore(key, blank, self.shape, id, &self.tree);
+            if !budget.expand(self.visited.len() + other.visited.len()) {
+                return None;
+            }
+            self.stats.processed_count += 1;
+
+            for mut child in current.get_child_nodes(dirs) {
+                if self.visited.contains_key(child.key()) {
+                    continue;
+                }
+
+                self.tree.add(&current, &mut child);
+
+                if let Some(&meeting) = other.visited.get(child.key())
+                    && best.is_none_or(|(_, b)| other.tree.depth(meeting) < other.tree.depth(b))
+                {
+                    best = Some((child.id(), meeting));
+                }
+
+                self.visited.insert(child.key().clone(), child.id());
+                next.push((child.key().clone(), child.blank(), child.id()));
+            }
+        }
+
+        self.stats.visited_count = self.visited.len();
+        self.frontier = next;
+        self.depth += 1;
+        best
+    }
+}
+
+impl BiBfs {
+    /// Joins the path from the start to `meet_forward` with the path from
+    /// `meet_backward` to the goal, both nodes holding the same state.
+    fn stitch(
+        forward: &Side,
+        meet_forward: NodeId,
+        backward: &Side,
+        meet_backward: NodeId,
+    ) -> Vec<Dir> {
+        let mut path = forward.tree.path(meet_forward);
+        let to_goal = backward.tree.path(meet_backward);
+        path.extend(to_goal.iter().rev().map(|dir| dir.reverse()));
+        path
+    }
+}
+
+impl Algorithm for BiBfs {
+    /// Executes a bidirectional breadth-first search to solve the puzzle.
+    ///
+    /// # Arguments
+    /// * `state` - The initial puzzle state to solve
+    /// * `config` - The move ordering and search limits to use
+    ///
+    /// # Returns
+    /// * `Some(Node)` containing the solved state if found within the limits
+    /// * `None` if no solution exists or a limit is reached, see `get_stopped_by`
+    ///
+    /// # Behavior
+    /// 1. Starts one BFS from the initial state and one from the solved board
+    /// 2. Expands a whole layer of the side with the smaller frontier
+    /// 3. Stops when a generated state was already reached by the other side
+    /// 4. Stitches both half-paths, reversing the backward one with `Dir::reverse`
+    /// 5. Records expansions of each side separately in `sides`
+    ///
+    /// # Performance
+    /// * Time: O(b^(d/2)) where b is branching factor, d is solution depth
+    /// * Space: O(b^(d/2)) for storing visited states of both sides
+    /// * Complete: Will find solution if one exists within the depth limit, if any
+    /// * Optimal: Finds shortest path solution
+    ///
+    fn run(&mut self, state: Problem, config: &SearchConfig) -> Option<Node> {
+        let start = Node::new(state.get()).expect("Board is invalid");
+        let goal = state.goal();
+
+        if start.is_solved(&goal) {
+            return Some(start);
+        }
+
+        let dir_order = config.order();
+        let mut budget = Budget::new(config, &start);
+
+        let mut forward = Side::new(start.clone());
+        let mut backward = Side::new(Node::new(goal.table()).unwrap());
+
+        while !forward.frontier.is_empty()
+            && !backward.frontier.is_empty()
+            && budget.within_depth(forward.depth + backward.depth)
+        {
+            let meeting = if forward.frontier.len() <= backward.frontier.len() {
+                forward.expand(&backward, dir_order, &mut budget)
+            } else {
+                backward
+                    .expand(&forward, dir_order, &mut budget)
+                    .map(|(b, f)| (f, b))
+            };
+
+            if budget.is_stopped() {
+                break;
+            }
+
+            self.set_sides(Sides {
+                forward: forward.stats,
+                backward: backward.stats,
+            });
+
+            if let Some((meet_forward, meet_backward)) = meeting {
+                let path = Self::stitch(&forward, meet_forward, &backward, meet_backward);
+                let solved = start.follow(&path).unwrap();
+
+                self.set_visited_count(forward.visited.len() + backward.visited.len());
+                self.set_processed_count(
+                    forward.stats.processed_count + backward.stats.processed_count,
+                );
+                self.set_reached_depth(solved.depth() as i32);
+                self.set_path(path);
+                return Some(solved);
+            }
+        }
+
+        self.set_visited_count(forward.visited.len() + backward.visited.len());
+        self.set_processed_count(forward.stats.processed_count + backward.stats.processed_count);
+        self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Problem;
+    use crate::core::SearchConfig;
+    use crate::core::Tile;
+
+    use super::Algorithm;
+    use super::BiBfs;
+    use crate::algorithms::Bfs;
+
+    // Helper function to set up a solved state
+    fn solved_state() -> Problem {
+        Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12], // Correct final state
+            vec![13, 14, 15, 0], // Correct final state
+        ])
+    }
+
+    // Helper function to set up an unsolved state
+    fn unsolved_state() -> Problem {
+        Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ])
+    }
+
+    // Test if bidirectional BFS finds a solved state immediately
+    #[test]
+    fn test_bibfs_immediate_solution() {
+        let mut bibfs = BiBfs::new();
+
+        let result = bibfs.run(solved_state(), &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().depth(), 0);
+    }
+
+    // Test if bidirectional BFS can solve a puzzle
+    #[test]
+    fn test_bibfs_solving_puzzle() {
+        let mut bibfs = BiBfs::new();
+
+        let result = bibfs.run(unsolved_state(), &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            solved_state()
+                .get()
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test if the stitched path is as short as the one found by BFS
+    #[test]
+    fn test_bibfs_optimal_solution() {
+        let mut bibfs = BiBfs::new();
+        let mut bfs = Bfs::new();
+
+        let bibfs_result = bibfs
+            .run(unsolved_state(), &SearchConfig::default())
+            .unwrap();
+        let bfs_result = bfs.run(unsolved_state(), &SearchConfig::default()).unwrap();
+
+        assert_eq!(bibfs_result.depth(), bfs_result.depth());
+        assert_eq!(bibfs.get_path().len(), bibfs_result.depth() as usize);
+    }
+
+    // Test if bidirectional BFS without a depth limit solves a board deeper than 20 moves
+    #[test]
+    fn test_bibfs_unbounded_depth() {
+        // One of the two hardest 3x3 boards, 31 moves from the goal
+        let deep_state = Problem::new(vec![vec![8, 6, 7], vec![2, 5, 4], vec![3, 0, 1]]);
+        let mut bibfs = BiBfs::new();
+
+        let result = bibfs.run(deep_state, &SearchConfig::default());
+        assert_eq!(result.unwrap().depth(), 31);
+        assert_eq!(bibfs.get_path().len(), 31);
+    }
+
+    // Test if both sides of the search are reported
+    #[test]
+    fn test_bibfs_sides() {
+        let mut bibfs = BiBfs::new();
+
+        bibfs
+            .run(unsolved_state(), &SearchConfig::default())
+            .unwrap();
+
+        let sides = bibfs.get_sides().unwrap();
+        assert!(sides.forward.processed_count > 0);
+        assert!(sides.backward.processed_count > 0);
+        assert_eq!(
+            bibfs.get_processed_count(),
+            sides.forward.processed_count + sides.backward.processed_count
+        );
+    }
+
+    // Test bidirectional BFS on a very small puzzle (e.g., 2x2)
+    #[test]
+    fn test_bibfs_small_puzzle() {
+        let small_unsolved_board = Problem::new(vec![
+            vec![1, 0],
+            vec![3, 2], // Misplaced pieces
+        ]);
+        let small_solved_board = Problem::new(vec![
+            vec![1, 2],
+            vec![3, 0], // Correct final state
+        ]);
+        let mut bibfs = BiBfs::new();
+
+        let result = bibfs.run(small_unsolved_board, &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            small_solved_board
+                .get()
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test bidirectional BFS on an empty board (should panic based on your implementation)
+    #[test]
+    #[should_panic]
+    fn test_bibfs_empty_board() {
+        let empty_board: Vec<Vec<Tile>> = Vec::new();
+        let mut bibfs = BiBfs::new();
+        bibfs.run(Problem::new(empty_board), &SearchConfig::default());
+    }
+}
@@ -2,11 +2,12 @@ use std::collections::HashMap;
 
 use algorithm_derive::Algorithm;
 
-use crate::core::{Algorithm, Order, Problem, algorithm::AlgorithmCommon, node::Node};
+use crate::core::{
+    Algorithm, Board, Dir, PackedBoard, Problem, SearchConfig, algorithm::AlgorithmCommon,
+    limits::Budget, node::Node,
+};
 
-use super::MAX_DEPTH;
-
-#[derive(Default, Algorithm)]
+#[derive(Default, Clone, Algorithm)]
 pub struct Dfs {
     common: AlgorithmCommon,
 }
@@ -16,79 +17,113 @@ impl Algorithm for Dfs {
     ///
     /// # Arguments
     /// * `state` - The initial puzzle state to solve
-    /// * `order` - The move ordering to use (must be `Order::Perm` variant)
+    /// * `config` - The move ordering and search limits to use
     ///
     /// # Returns
-    /// * `Some(Node)` containing the solved state if found within `MAX_DEPTH`
-    /// * `None` if no solution exists or maximum depth is reached
+    /// * `Some(Node)` containing the solved state if found within the limits
+    /// * `None` if no solution exists or a limit is reached, see `get_stopped_by`
     ///
     /// # Behavior
-    /// 1. Uses a stack for LIFO processing (depth-first exploration)
-    /// 2. Expands nodes in reverse order of specified directions (for DFS)
+    /// 1. Explores the moves of every board in the specified order, following the
+    ///    first possible one before trying the next (depth-first exploration)
+    /// 2. Keeps a single board, applying a move to enter a child and undoing it to
+    ///    backtrack, so no node is allocated while searching
     /// 3. Tracks visited states with their depth to prune worse paths
     /// 4. Updates algorithm statistics upon solution discovery
-    /// 5. Enforces `MAX_DEPTH` limit to prevent infinite recursion
-    ///
-    /// # Panics
-    /// Will panic if `order` is not the `Order::Perm` variant
+    /// 5. Enforces the configured depth limit, without one the visited states alone
+    ///    bound the search, which may then return a very long path
+    /// 6. Stops once the node, time or memory limit is exceeded
     ///
     /// # Performance Characteristics
     /// * Time complexity: O(b^m) where b is branching factor, m is maximum depth
-    /// * Space complexity: O(b*m) for stack storage
+    /// * Space complexity: O(m) for the current path, plus the visited states
     /// * Not complete (may miss solutions due to depth limit)
     /// * Not optimal (may find suboptimal solutions)
     ///
     /// # Implementation Notes
-    /// - Uses reversed move ordering to maintain proper DFS exploration order
+    /// - Every board on the current path remembers which of its moves to try next
     /// - Depth-checking prevents redundant exploration of worse paths
-    /// - Statistics are updated only when solution is found
+    /// - Statistics are updated when a solution is found or the search stops
     ///
-    fn run(&mut self, state: Problem, order: &Order) -> Option<Node> {
-        let mut visited = HashMap::<u64, u8>::new();
-        let mut stack = Vec::new();
-        let current = Node::new(state.get().clone());
+    fn run(&mut self, state: Problem, config: &SearchConfig) -> Option<Node> {
+        let start = Node::new(state.get().clone()).expect("Board is invalid");
+        let goal = state.goal();
 
-        if current.is_solved() {
-            return Some(current);
+        if start.is_solved(&goal) {
+            return Some(start);
         }
 
-        let dir_order = match order {
-            Order::Perm(dirs) => dirs,
-            _ => panic!("Podano nieprawidłowy typ Order!"),
-        };
+        let dir_order = config.order();
+        let mut budget = Budget::new(config, &start);
+        let mut board = Board::new(&start, &goal);
+        let mut visited = HashMap::<PackedBoard, u16>::new();
+        visited.insert(board.key(), 0);
 
-        stack.push(current.clone());
-        visited.insert(current.hash_code(), current.depth());
+        // Moves leading to the current board and, for each board on the way, the
+        // index in `dir_order` of the next move to try
+        let mut path: Vec<Dir> = Vec::new();
+        let mut next: Vec<usize> = Vec::new();
         let mut count = 0;
 
-        while let Some(current) = stack.pop() {
-            count += 1;
-
-            if current.depth() >= MAX_DEPTH {
-                continue;
+        'search: loop {
+            if !budget.expand(visited.len()) {
+                break;
             }
+            count += 1;
 
-            for child in current.get_child_nodes_reverse(*dir_order) {
-                if let Some(&existing_depth) = visited.get(&child.hash_code()) {
-                    if child.depth() >= existing_depth {
-                        continue;
+            let first = if budget.within_depth(path.len() as u16) {
+                0
+            } else {
+                dir_order.len()
+            };
+            next.push(first);
+
+            // Enter the next unseen child, backtracking from exhausted boards
+            while let Some(tried) = next.last_mut() {
+                let Some(&dir) = dir_order.get(*tried) else {
+                    next.pop();
+                    if let Some(dir) = path.pop() {
+                        board.undo(dir);
                     }
+                    continue;
+                };
+                *tried += 1;
+
+                if !board.apply(dir) {
+                    continue;
+                }
+
+                let depth = path.len() as u16 + 1;
+                let key = board.key();
+                if let Some(&existing_depth) = visited.get(&key)
+                    && depth >= existing_depth
+                {
+                    board.undo(dir);
+                    continue;
                 }
 
-                if child.is_solved() {
+                path.push(dir);
+
+                if board.is_solved() {
                     self.set_visited_count(visited.len());
-                    self.set_reached_depth(child.depth() as i16);
-                    self.set_result_len(child.depth());
+                    self.set_reached_depth(depth as i32);
                     self.set_processed_count(count);
-                    return Some(child);
+                    let solved = start.follow(&path);
+                    self.set_path(path);
+                    return solved;
                 }
 
-                visited.insert(child.hash_code(), child.depth());
-                stack.push(child);
+                visited.insert(key, depth);
+                continue 'search;
             }
+
+            break;
         }
 
+        self.set_visited_count(visited.len());
+        self.set_processed_count(count);
         self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
         None
     }
 }
@@ -96,7 +131,7 @@ impl Algorithm for Dfs {
 #[cfg(test)]
 mod tests {
 
-    use crate::core::{Algorithm, Dir, Problem, order::Order};
+    use crate::core::{Algorithm, Problem, SearchConfig, Tile};
 
     use super::Dfs;
     // Helper function to set up a solved state
@@ -126,16 +161,16 @@ mod tests {
         let mut dfs = Dfs::new();
 
         // Since the initial state is solved, BFS should return the solved state immediately
-        let result = dfs.run(solved_state.clone(), &Order::Perm(Dir::values()));
+        let result = dfs.run(solved_state.clone(), &SearchConfig::default());
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -145,17 +180,19 @@ mod tests {
         let unsolved_state = unsolved_state();
         let mut bfs = Dfs::new();
 
-        // BFS should be able to find the solved state
-        let result = bfs.run(unsolved_state.clone(), &Order::Perm(Dir::values()));
+        // DFS should be able to find the solved state, if kept from wandering
+        // through the whole 4x4 state space
+        let config = SearchConfig::builder().max_depth(20).build().unwrap();
+        let result = bfs.run(unsolved_state.clone(), &config);
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state()
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -175,16 +212,16 @@ mod tests {
         let mut bfs = Dfs::new();
 
         // BFS should return the solved state for the 2x2 puzzle
-        let result = bfs.run(unsolved_state, &Order::Perm(Dir::values()));
+        let result = bfs.run(unsolved_state, &SearchConfig::default());
         assert!(result.is_some());
         assert_eq!(
-            *result.unwrap().get_board(),
+            result.unwrap().get_board(),
             solved_state
                 .get()
                 .iter()
                 .flatten()
                 .copied()
-                .collect::<Vec<u8>>()
+                .collect::<Vec<Tile>>()
         );
     }
 
@@ -192,8 +229,8 @@ mod tests {
     #[test]
     #[should_panic]
     fn test_bfs_empty_board() {
-        let empty_board: Vec<Vec<u8>> = Vec::new();
+        let empty_board: Vec<Vec<Tile>> = Vec::new();
         let mut bfs = Dfs::new(); // This should panic because the state is empty
-        bfs.run(Problem::new(empty_board), &Order::Perm(Dir::values()));
+        bfs.run(Problem::new(empty_board), &SearchConfig::default());
     }
 }
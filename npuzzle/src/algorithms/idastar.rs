@@ -0,0 +1,308 @@
+use algorithm_derive::Algorithm;
+
+use crate::core::{
+    Algorithm, AlgorithmCommon, Board, Dir, Iteration, Problem, SearchConfig, limits::Budget,
+    node::Node,
+};
+use crate::heuristics::Heuristic;
+
+#[derive(Default, Clone, Algorithm)]
+pub struct IdaStar {
+    common: AlgorithmCommon,
+}
+
+// Outcome of a single bounded depth-first pass
+enum Bound {
+    Found,
+    Exceeded(usize),
+    Stopped,
+}
+
+// A single bounded pass, moving one board along the searched path
+struct Pass<'a> {
+    board: Board,
+    path: Vec<Dir>,
+    threshold: usize,
+    heuristic: &'a dyn Heuristic,
+    dirs: [Dir; 4],
+    iteration: Iteration,
+    budget: &'a mut Budget,
+}
+
+impl Pass<'_> {
+    // Leaves the moves to the solved board in `path` if it is found, `estimate`
+    // is the heuristic value of the current board
+    fn search(&mut self, estimate: usize) -> Bound {
+        let depth = self.path.len();
+        let cost = depth + estimate;
+        if cost > self.threshold {
+            return Bound::Exceeded(cost);
+        }
+
+        if self.board.is_solved() {
+            return Bound::Found;
+        }
+
+        if !self.budget.within_depth(depth as u16) {
+            return Bound::Exceeded(usize::MAX);
+        }
+
+        if !self.budget.expand(depth + 1) {
+            return Bound::Stopped;
+        }
+
+        self.iteration.processed_count += 1;
+        let mut next_threshold = usize::MAX;
+
+        for dir in self.dirs {
+            // Undoing the previous move can never lead to a shorter path
+            if self.path.last() == Some(&dir.reverse()) {
+                continue;
+            }
+            let Some(slide) = self.board.slide(dir) else {
+                continue;
+            };
+
+            let update =
+                self.heuristic
+                    .update(self.board.tiles(), self.board.goal(), estimate, slide);
+            self.board.apply(dir);
+            self.path.push(dir);
+            self.iteration.visited_count += 1;
+
+            let child_estimate = update.unwrap_or_else(|| {
+                self.heuristic
+                    .estimate(self.board.tiles(), self.board.goal())
+            });
+            match self.search(child_estimate) {
+                Bound::Found => return Bound::Found,
+                Bound::Stopped => return Bound::Stopped,
+                Bound::Exceeded(cost) => next_threshold = next_threshold.min(cost),
+            }
+
+            self.path.pop();
+            self.board.undo(dir);
+        }
+
+        Bound::Exceeded(next_threshold)
+    }
+}
+
+impl Algorithm for IdaStar {
+    /// Executes the iterative-deepening A* (IDA*) algorithm to solve the given puzzle problem.
+    ///
+    /// # Arguments
+    /// * `problem` - The initial puzzle state to solve
+    /// * `config` - The heuristic (e.g., Manhattan distance), move ordering and search limits to use
+    ///
+    /// # Returns
+    /// * `Some(Node)` containing the solved state if a solution is found
+    /// * `None` if the search space is exhausted, a limit is reached (see `get_stopped_by`)
+    ///   or `config` has no heuristic
+    ///
+    /// # Behavior
+    /// 1.Starts with a threshold equal to the heuristic estimate of the initial state
+    /// 2.Runs a depth-first search pruning every node whose (heuristic + path cost) exceeds it,
+    ///   applying and undoing moves on a single board
+    /// 3.Raises the threshold to the smallest pruned cost and repeats
+    /// 4.Records threshold, visited and processed counts of every pass in `iterations`
+    ///
+    /// # Performance Characteristics
+    /// * Time complexity: O(b^d) where b is branching factor, d is solution depth
+    /// * Space complexity: O(d), only the current path is kept in memory
+    /// * Complete: Will find solution if one exists
+    /// * Optimal: Finds shortest path when using admissible heuristic
+    ///
+    fn run(&mut self, problem: Problem, config: &SearchConfig) -> Option<Node> {
+        let Some(heuristic) = config.heuristic() else {
+            self.set_reached_depth(-1);
+            return None;
+        };
+
+        let dirs = config.order();
+
+        let state = Node::new(problem.get()).expect("Board is invalid");
+        let goal = problem.goal();
+        let mut budget = Budget::new(config, &state);
+        self.iterations.clear();
+
+        let estimate = heuristic.estimate(&state.get_board(), &goal);
+        let mut pass = Pass {
+            board: Board::new(&state, &goal),
+            path: Vec::new(),
+            threshold: estimate,
+            heuristic: heuristic.as_ref(),
+            dirs,
+            iteration: Iteration::default(),
+            budget: &mut budget,
+        };
+
+        loop {
+            pass.iteration = Iteration {
+                threshold: pass.threshold,
+                ..Iteration::default()
+            };
+
+            let bound = pass.search(estimate);
+            self.push_iteration(pass.iteration);
+
+            match bound {
+                Bound::Found => {
+                    self.common.count_iterations();
+                    self.set_reached_depth(pass.path.len() as i32);
+                    let solved = state.follow(&pass.path);
+                    self.set_path(pass.path);
+                    return solved;
+                }
+                Bound::Exceeded(usize::MAX) | Bound::Stopped => break,
+                Bound::Exceeded(next) => pass.threshold = next,
+            }
+        }
+
+        self.common.count_iterations();
+        self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
+        None
+    }
+
+    fn is_informed(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::heuristics::Manhattan;
+
+    use crate::core::{Algorithm, Problem, SearchConfig, Tile};
+
+    use super::IdaStar;
+    use crate::algorithms::Bfs;
+
+    // Helper function to set up a solved state
+    fn solved_state() -> Problem {
+        Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12], // Correct final state
+            vec![13, 14, 15, 0], // Correct final state
+        ])
+    }
+
+    // Helper function to set up an unsolved state
+    fn unsolved_state() -> Problem {
+        Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ])
+    }
+
+    // Test if IDA* finds a solved state immediately
+    #[test]
+    fn test_idastar_immediate_solution() {
+        let solved_state = solved_state();
+        let mut idastar = IdaStar::new();
+
+        let result = idastar.run(solved_state.clone(), &SearchConfig::informed(Manhattan));
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().depth(), 0);
+    }
+
+    // Test if IDA* can solve a puzzle
+    #[test]
+    fn test_idastar_solving_puzzle() {
+        let unsolved_state = unsolved_state();
+        let mut idastar = IdaStar::new();
+
+        let result = idastar.run(unsolved_state.clone(), &SearchConfig::informed(Manhattan));
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            solved_state()
+                .get()
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test if IDA* finds a path as short as the one found by BFS
+    #[test]
+    fn test_idastar_optimal_solution() {
+        let mut idastar = IdaStar::new();
+        let mut bfs = Bfs::new();
+
+        let ida_result = idastar
+            .run(unsolved_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+        let bfs_result = bfs.run(unsolved_state(), &SearchConfig::default()).unwrap();
+
+        assert_eq!(ida_result.depth(), bfs_result.depth());
+    }
+
+    // Test if every threshold pass is recorded
+    #[test]
+    fn test_idastar_iterations() {
+        let mut idastar = IdaStar::new();
+
+        idastar
+            .run(unsolved_state(), &SearchConfig::informed(Manhattan))
+            .unwrap();
+
+        let iterations = idastar.get_iterations();
+        assert!(!iterations.is_empty());
+        assert!(
+            iterations
+                .windows(2)
+                .all(|w| w[0].threshold < w[1].threshold)
+        );
+        assert_eq!(
+            iterations.last().unwrap().threshold,
+            idastar.get_result_len() as usize
+        );
+        assert_eq!(
+            idastar.get_processed_count(),
+            iterations.iter().map(|i| i.processed_count).sum::<usize>()
+        );
+    }
+
+    // Test IDA* on a very small puzzle (e.g., 2x2)
+    #[test]
+    fn test_idastar_small_puzzle() {
+        let small_unsolved_board = Problem::new(vec![
+            vec![1, 0],
+            vec![3, 2], // Misplaced pieces
+        ]);
+        let small_solved_board = [
+            vec![1, 2],
+            vec![3, 0], // Correct final state
+        ];
+        let mut idastar = IdaStar::new();
+
+        let result = idastar.run(small_unsolved_board, &SearchConfig::informed(Manhattan));
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            small_solved_board
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test IDA* on an empty board (should panic based on your implementation)
+    #[test]
+    #[should_panic]
+    fn test_idastar_empty_board() {
+        let empty_board: Vec<Vec<Tile>> = Vec::new();
+        let mut idastar = IdaStar::new();
+        idastar.run(
+            Problem::new(empty_board),
+            &SearchConfig::informed(Manhattan),
+        );
+    }
+}
@@ -0,0 +1,273 @@
+use algorithm_derive::Algorithm;
+
+use crate::core::{
+    Algorithm, AlgorithmCommon, Board, Dir, Iteration, Problem, SearchConfig, limits::Budget,
+    node::Node,
+};
+
+#[derive(Default, Clone, Algorithm)]
+pub struct Iddfs {
+    common: AlgorithmCommon,
+}
+
+// Outcome of a single depth-limited pass
+enum Step {
+    Found,
+    Cutoff,
+    Exhausted,
+    Stopped,
+}
+
+// A single depth-limited pass, moving one board along the searched path
+struct Pass<'a> {
+    board: Board,
+    path: Vec<Dir>,
+    bound: u16,
+    dirs: [Dir; 4],
+    iteration: Iteration,
+    budget: &'a mut Budget,
+}
+
+impl Pass<'_> {
+    // Leaves the moves to the solved board in `path` if it is found
+    fn search(&mut self) -> Step {
+        if self.board.is_solved() {
+            return Step::Found;
+        }
+
+        let depth = self.path.len() as u16;
+        if depth >= self.bound {
+            return Step::Cutoff;
+        }
+
+        if !self.budget.expand(depth as usize + 1) {
+            return Step::Stopped;
+        }
+
+        self.iteration.processed_count += 1;
+        let mut cutoff = false;
+
+        for dir in self.dirs {
+            // Undoing the previous move only revisits the parent
+            if self.path.last() == Some(&dir.reverse()) || !self.board.apply(dir) {
+                continue;
+            }
+            self.path.push(dir);
+            self.iteration.visited_count += 1;
+
+            match self.search() {
+                Step::Found => return Step::Found,
+                Step::Stopped => return Step::Stopped,
+                Step::Cutoff => cutoff = true,
+                Step::Exhausted => {}
+            }
+
+            self.path.pop();
+            self.board.undo(dir);
+        }
+
+        if cutoff {
+            Step::Cutoff
+        } else {
+            Step::Exhausted
+        }
+    }
+}
+
+impl Algorithm for Iddfs {
+    /// Executes an iterative-deepening depth-first search (IDDFS) to solve the puzzle.
+    ///
+    /// # Arguments
+    /// * `state` - The initial puzzle state to solve
+    /// * `config` - The move ordering and search limits to use, the depth limit bounds the last pass
+    ///
+    /// # Returns
+    /// * `Some(Node)` containing the solved state at the shallowest depth it exists
+    /// * `None` if the search space is exhausted or a limit is reached, see `get_stopped_by`
+    ///
+    /// # Behavior
+    /// 1. Runs a depth-limited DFS with a bound of 0
+    /// 2. Expands nodes according to specified move ordering, skipping immediate backtracks,
+    ///    by applying and undoing moves on a single board
+    /// 3. Increases the bound by one and repeats until the goal is found
+    /// 4. Records bound, visited and processed counts of every pass in `iterations`
+    ///
+    /// # Performance
+    /// * Time: O(b^d) where b is branching factor, d is solution depth
+    /// * Space: O(d), only the current path is kept in memory
+    /// * Complete: Will find solution if one exists
+    /// * Optimal: Finds shortest path solution
+    ///
+    fn run(&mut self, state: Problem, config: &SearchConfig) -> Option<Node> {
+        let dir_order = config.order();
+
+        let current = Node::new(state.get()).expect("Board is invalid");
+        let goal = state.goal();
+        let mut budget = Budget::new(config, &current);
+        self.iterations.clear();
+
+        let mut pass = Pass {
+            board: Board::new(&current, &goal),
+            path: Vec::new(),
+            bound: 0,
+            dirs: dir_order,
+            iteration: Iteration::default(),
+            budget: &mut budget,
+        };
+
+        loop {
+            pass.iteration = Iteration {
+                threshold: pass.bound as usize,
+                ..Iteration::default()
+            };
+
+            let step = pass.search();
+            self.push_iteration(pass.iteration);
+
+            match step {
+                Step::Found => {
+                    self.common.count_iterations();
+                    self.set_reached_depth(pass.path.len() as i32);
+                    let solved = current.follow(&pass.path);
+                    self.set_path(pass.path);
+                    return solved;
+                }
+                Step::Cutoff if pass.budget.within_depth(pass.bound) => pass.bound += 1,
+                _ => break,
+            }
+        }
+
+        self.common.count_iterations();
+        self.set_reached_depth(-1);
+        self.set_stopped_by(budget.stopped_by());
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::heuristics::Manhattan;
+
+    use crate::core::{Algorithm, ConfigError, Problem, SearchConfig, Tile, solver::IddfsSolver};
+
+    use super::Iddfs;
+    use crate::algorithms::Bfs;
+
+    // Helper function to set up a solved state
+    fn solved_state() -> Problem {
+        Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12], // Correct final state
+            vec![13, 14, 15, 0], // Correct final state
+        ])
+    }
+
+    // Helper function to set up an unsolved state
+    fn unsolved_state() -> Problem {
+        Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ])
+    }
+
+    // Test if IDDFS finds a solved state immediately
+    #[test]
+    fn test_iddfs_immediate_solution() {
+        let mut iddfs = Iddfs::new();
+
+        let result = iddfs.run(solved_state(), &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().depth(), 0);
+    }
+
+    // Test if IDDFS can solve a puzzle
+    #[test]
+    fn test_iddfs_solving_puzzle() {
+        let mut iddfs = Iddfs::new();
+
+        let result = iddfs.run(unsolved_state(), &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            solved_state()
+                .get()
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test if IDDFS finds a path as short as the one found by BFS
+    #[test]
+    fn test_iddfs_optimal_solution() {
+        let mut iddfs = Iddfs::new();
+        let mut bfs = Bfs::new();
+
+        let iddfs_result = iddfs
+            .run(unsolved_state(), &SearchConfig::default())
+            .unwrap();
+        let bfs_result = bfs.run(unsolved_state(), &SearchConfig::default()).unwrap();
+
+        assert_eq!(iddfs_result.depth(), bfs_result.depth());
+    }
+
+    // Test if every depth bound is recorded
+    #[test]
+    fn test_iddfs_iterations() {
+        let mut iddfs = Iddfs::new();
+
+        iddfs
+            .run(unsolved_state(), &SearchConfig::default())
+            .unwrap();
+
+        let iterations = iddfs.get_iterations();
+        assert_eq!(iterations.len(), iddfs.get_result_len() as usize + 1);
+        assert!(
+            iterations
+                .iter()
+                .enumerate()
+                .all(|(bound, i)| i.threshold == bound)
+        );
+        assert_eq!(
+            iddfs.get_visited_count(),
+            iterations.iter().map(|i| i.visited_count).sum::<usize>()
+        );
+    }
+
+    // Test IDDFS on a very small puzzle (e.g., 2x2)
+    #[test]
+    fn test_iddfs_small_puzzle() {
+        let small_unsolved_board = Problem::new(vec![
+            vec![1, 0],
+            vec![3, 2], // Misplaced pieces
+        ]);
+        let small_solved_board = Problem::new(vec![
+            vec![1, 2],
+            vec![3, 0], // Correct final state
+        ]);
+        let mut iddfs = Iddfs::new();
+
+        let result = iddfs.run(small_unsolved_board, &SearchConfig::default());
+        assert!(result.is_some());
+        assert_eq!(
+            result.unwrap().get_board(),
+            small_solved_board
+                .get()
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<Tile>>()
+        );
+    }
+
+    // Test if a solver rejects IDDFS configured with a heuristic
+    #[test]
+    fn test_iddfs_rejects_heuristic() {
+        let result = IddfsSolver::new(SearchConfig::informed(Manhattan));
+        assert_eq!(result.err(), Some(ConfigError::UnusedHeuristic));
+    }
+}
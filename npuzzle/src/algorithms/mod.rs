@@ -1,9 +1,13 @@
 pub mod astar;
 pub mod bfs;
+pub mod bibfs;
 pub mod dfs;
+pub mod idastar;
+pub mod iddfs;
 
-pub use astar::AStar;
+pub use astar::{AStar, Weighting};
 pub use bfs::Bfs;
+pub use bibfs::BiBfs;
 pub use dfs::Dfs;
-
-pub const MAX_DEPTH: u8 = 20;
+pub use idastar::IdaStar;
+pub use iddfs::Iddfs;
@@ -1,6 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
-use super::{Order, Problem, node::Node};
+use super::{ConfigError, Dir, Limit, Problem, SearchConfig, node::Node};
 
 pub trait Algorithm: Deref<Target = AlgorithmCommon> + DerefMut {
     fn new() -> Self
@@ -10,9 +10,32 @@ pub trait Algorithm: Deref<Target = AlgorithmCommon> + DerefMut {
     {
         Self::default()
     }
-    fn run(&mut self, state: Problem, order: &Order) -> Option<Node>;
 
-    fn get_reached_depth(&self) -> i16 {
+    /// Searches for the solved board, returning `None` if the search ends
+    /// without reaching it. The moves leading to the returned node are
+    /// available from `get_path`.
+    ///
+    /// # Panics
+    /// Panics if the board is invalid, see `Node::new`. `Solver::solve` checks
+    /// the board before running the search.
+    fn run(&mut self, state: Problem, config: &SearchConfig) -> Option<Node>;
+
+    /// Whether the search is guided by the heuristic of the configuration.
+    fn is_informed(&self) -> bool {
+        false
+    }
+
+    /// Checks that `config` provides what the algorithm needs, and nothing
+    /// it would silently ignore.
+    fn validate(&self, config: &SearchConfig) -> Result<(), ConfigError> {
+        match (self.is_informed(), config.heuristic()) {
+            (true, None) => Err(ConfigError::MissingHeuristic),
+            (false, Some(_)) => Err(ConfigError::UnusedHeuristic),
+            _ => Ok(()),
+        }
+    }
+
+    fn get_reached_depth(&self) -> i32 {
         self.reached_depth
     }
 
@@ -24,10 +47,30 @@ pub trait Algorithm: Deref<Target = AlgorithmCommon> + DerefMut {
         self.processed_count
     }
 
-    fn get_result_len(&self) -> u8 {
+    fn get_result_len(&self) -> u16 {
         self.result_len
     }
 
+    fn get_path(&self) -> &[Dir] {
+        &self.path
+    }
+
+    fn get_iterations(&self) -> &[Iteration] {
+        &self.iterations
+    }
+
+    fn get_sides(&self) -> Option<Sides> {
+        self.sides
+    }
+
+    fn get_suboptimality_bound(&self) -> Option<f64> {
+        self.suboptimality_bound
+    }
+
+    fn get_stopped_by(&self) -> Option<Limit> {
+        self.stopped_by
+    }
+
     fn set_processed_count(&mut self, count: usize) {
         self.processed_count = count;
     }
@@ -36,25 +79,76 @@ pub trait Algorithm: Deref<Target = AlgorithmCommon> + DerefMut {
         self.visited_count = count;
     }
 
-    fn set_reached_depth(&mut self, depth: i16) {
+    fn set_reached_depth(&mut self, depth: i32) {
         self.reached_depth = depth;
     }
 
-    fn set_result_len(&mut self, len: u8) {
+    fn set_result_len(&mut self, len: u16) {
         self.result_len = len;
     }
+
+    /// Records the moves of the solution, also setting its length.
+    fn set_path(&mut self, path: Vec<Dir>) {
+        self.result_len = path.len() as u16;
+        self.path = path;
+    }
+
+    fn push_iteration(&mut self, iteration: Iteration) {
+        self.iterations.push(iteration);
+    }
+
+    fn set_sides(&mut self, sides: Sides) {
+        self.sides = Some(sides);
+    }
+
+    fn set_stopped_by(&mut self, limit: Option<Limit>) {
+        self.stopped_by = limit;
+    }
+}
+
+/// Statistics of a single pass of an iterative-deepening algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Iteration {
+    pub threshold: usize,
+    pub visited_count: usize,
+    pub processed_count: usize,
 }
 
-#[derive(Default)]
+/// Statistics of one direction of a bidirectional search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SideStats {
+    pub visited_count: usize,
+    pub processed_count: usize,
+}
+
+/// Statistics of both directions of a bidirectional search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sides {
+    pub forward: SideStats,
+    pub backward: SideStats,
+}
+
+#[derive(Default, Clone)]
 pub struct AlgorithmCommon {
     pub visited_count: usize,
     pub processed_count: usize,
-    pub reached_depth: i16,
-    pub result_len: u8,
+    pub reached_depth: i32,
+    pub result_len: u16,
+    pub path: Vec<Dir>,
+    pub iterations: Vec<Iteration>,
+    pub sides: Option<Sides>,
+    pub suboptimality_bound: Option<f64>,
+    pub stopped_by: Option<Limit>,
 }
 
 impl AlgorithmCommon {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Sets the totals from the statistics of all passes.
+    pub(crate) fn count_iterations(&mut self) {
+        self.visited_count = self.iterations.iter().map(|i| i.visited_count).sum();
+        self.processed_count = self.iterations.iter().map(|i| i.processed_count).sum();
+    }
 }
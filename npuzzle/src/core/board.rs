@@ -0,0 +1,246 @@
+use super::{Dir, Goal, PackedBoard, Shape, Tile, node::Node};
+use crate::heuristics::{Heuristic, Manhattan, Slide, manhattan::distance};
+
+/// A single board changed in place by moving the blank.
+///
+/// Depth-first searches follow one path at a time, so instead of creating a
+/// `Node` for every child they `apply` a move, search below it and `undo` it,
+/// without allocating. The Manhattan distance of the board to its goal is
+/// kept up to date from the tile each move shifts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Board {
+    tiles: Vec<Tile>,
+    goal: Goal,
+    blank: u16,
+    manhattan: usize,
+}
+
+impl Board {
+    /// Board holding the state of `node`, solved when it reaches `goal`.
+    pub fn new(node: &Node, goal: &Goal) -> Self {
+        debug_assert_eq!(node.shape(), goal.shape());
+
+        let tiles = node.get_board();
+        Self {
+            manhattan: Manhattan.estimate(&tiles, goal),
+            tiles,
+            goal: goal.clone(),
+            blank: node.blank(),
+        }
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.goal.shape()
+    }
+
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// Index of the empty tile.
+    pub fn blank(&self) -> u16 {
+        self.blank
+    }
+
+    /// Manhattan distance of the current state, see `Manhattan`.
+    pub fn manhattan(&self) -> usize {
+        self.manhattan
+    }
+
+    /// Change of the Manhattan distance if the blank was moved in `dir`,
+    /// `None` if it cannot be.
+    pub fn manhattan_delta(&self, dir: Dir) -> Option<isize> {
+        let target = self.target(dir)?;
+        let tile = self.tiles[target as usize];
+
+        // The tile takes the place of the blank
+        Some(
+            distance(tile, self.blank as usize, &self.goal) as isize
+                - distance(tile, target as usize, &self.goal) as isize,
+        )
+    }
+
+    /// Index of the tile the blank would swap with when moved in `dir`.
+    pub fn target(&self, dir: Dir) -> Option<u16> {
+        let Shape { height, width } = self.shape();
+        let (height, width) = (height as u16, width as u16);
+        let (row, col) = (self.blank / width, self.blank % width);
+
+        match dir {
+            Dir::Up if row > 0 => Some(self.blank - width),
+            Dir::Down if row < height - 1 => Some(self.blank + width),
+            Dir::Left if col > 0 => Some(self.blank - 1),
+            Dir::Right if col < width - 1 => Some(self.blank + 1),
+            _ => None,
+        }
+    }
+
+    /// Moves the blank in `dir`.
+    ///
+    /// # Returns
+    /// * `false` if the blank is at the edge, the board is left unchanged
+    pub fn apply(&mut self, dir: Dir) -> bool {
+        let Some(slide) = self.slide(dir) else {
+            return false;
+        };
+
+        self.manhattan = Manhattan
+            .update(&self.tiles, &self.goal, self.manhattan, slide)
+            .expect("Manhattan distance is updated incrementally");
+        self.tiles.swap(slide.from, slide.to);
+        self.blank = slide.from as u16;
+        true
+    }
+
+    /// The tile moving into the blank when the blank is moved in `dir`.
+    pub fn slide(&self, dir: Dir) -> Option<Slide> {
+        let target = self.target(dir)?;
+
+        Some(Slide::new(
+            &self.tiles,
+            self.blank as usize,
+            target as usize,
+        ))
+    }
+
+    /// Takes back a move in `dir` made by `apply`.
+    pub fn undo(&mut self, dir: Dir) {
+        let undone = self.apply(dir.reverse());
+        debug_assert!(undone, "Move {} was not applied", dir);
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.tiles == self.goal.tiles()
+    }
+
+    /// Exact key of the current state, see `Node::key`.
+    pub fn key(&self) -> PackedBoard {
+        PackedBoard::pack(&self.tiles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Board;
+    use crate::core::{Dir, Goal, Shape, node::Node};
+    use crate::heuristics::{Heuristic, Manhattan};
+
+    fn board() -> Board {
+        let node = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]).unwrap();
+        Board::new(&node, &Goal::standard(node.shape()))
+    }
+
+    // Test if applying a move matches creating the child node
+    #[test]
+    fn test_apply_matches_child_node() {
+        let node = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]).unwrap();
+
+        let goal = Goal::standard(node.shape());
+
+        for dir in Dir::values() {
+            let mut board = Board::new(&node, &goal);
+            assert!(board.apply(dir));
+
+            let child = node.get_node_for_move(dir).unwrap();
+            assert_eq!(board, Board::new(&child, &goal));
+            assert_eq!(board.key(), *child.key());
+        }
+    }
+
+    // Test if undo restores the board after a sequence of moves
+    #[test]
+    fn test_undo() {
+        let mut board = board();
+        let original = board.clone();
+        let moves = [Dir::Up, Dir::Left, Dir::Down, Dir::Down, Dir::Right];
+
+        for dir in moves {
+            assert!(board.apply(dir));
+        }
+        for dir in moves.into_iter().rev() {
+            board.undo(dir);
+        }
+
+        assert_eq!(board, original);
+    }
+
+    // Test if the Manhattan distance kept by the board matches full recomputation
+    #[test]
+    fn test_manhattan_updates() {
+        let mut board = board();
+        let moves = [
+            Dir::Up,
+            Dir::Left,
+            Dir::Down,
+            Dir::Down,
+            Dir::Right,
+            Dir::Right,
+            Dir::Up,
+        ];
+
+        for dir in moves {
+            let delta = board.manhattan_delta(dir).unwrap();
+            let before = board.manhattan();
+            assert!(board.apply(dir));
+
+            assert_eq!(
+                board.manhattan(),
+                Manhattan.estimate(board.tiles(), board.goal())
+            );
+            assert_eq!(board.manhattan() as isize - before as isize, delta);
+        }
+        for dir in moves.into_iter().rev() {
+            board.undo(dir);
+            assert_eq!(
+                board.manhattan(),
+                Manhattan.estimate(board.tiles(), board.goal())
+            );
+        }
+    }
+
+    // Test if moves on a board taller than wide stop at its own edges
+    #[test]
+    fn test_apply_on_rectangular_board() {
+        let node = Node::new(vec![vec![1, 2], vec![3, 4], vec![5, 0]]).unwrap();
+        let mut board = Board::new(&node, &Goal::standard(node.shape()));
+
+        assert!(!board.apply(Dir::Right));
+        assert!(board.apply(Dir::Up));
+        assert!(board.apply(Dir::Up));
+        assert!(!board.apply(Dir::Up));
+        assert_eq!(board.tiles(), [1, 0, 3, 2, 5, 4]);
+        assert_eq!(
+            board.manhattan(),
+            Manhattan.estimate(board.tiles(), board.goal())
+        );
+    }
+
+    // Test if the board is solved on its own goal and tracks the distance to it
+    #[test]
+    fn test_custom_goal() {
+        let goal = Goal::spiral(Shape::square(3));
+        let node = Node::new(vec![vec![1, 2, 3], vec![8, 4, 0], vec![7, 6, 5]]).unwrap();
+        let mut board = Board::new(&node, &goal);
+
+        assert!(!board.is_solved());
+        assert_eq!(board.manhattan(), 1);
+        assert!(board.apply(Dir::Left));
+        assert!(board.is_solved());
+        assert_eq!(board.manhattan(), 0);
+    }
+
+    // Test if a move off the edge leaves the board unchanged
+    #[test]
+    fn test_apply_at_edge() {
+        let mut board = board();
+        assert!(board.apply(Dir::Up));
+
+        let before = board.clone();
+        assert!(!board.apply(Dir::Up));
+        assert_eq!(board, before);
+    }
+}
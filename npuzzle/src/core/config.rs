@@ -0,0 +1,250 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use super::{CancelToken, Dir, Goal, SearchLimits, Shape};
+use crate::heuristics::Heuristic;
+
+/// Everything a search is run with, apart from the puzzle itself.
+///
+/// The move order, the heuristic and the limits are set independently with
+/// `SearchConfig::builder`; which of them an algorithm needs is checked when a
+/// `Solver` is constructed.
+#[derive(Clone)]
+pub struct SearchConfig {
+    order: [Dir; 4],
+    heuristic: Option<Arc<dyn Heuristic>>,
+    limits: SearchLimits,
+    cancel: CancelToken,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            order: Dir::values(),
+            heuristic: None,
+            limits: SearchLimits::default(),
+            cancel: CancelToken::default(),
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn builder() -> SearchConfigBuilder {
+        SearchConfigBuilder::default()
+    }
+
+    /// Default move order and limits with the given heuristic.
+    pub fn informed(heuristic: impl Heuristic + 'static) -> Self {
+        Self {
+            heuristic: Some(Arc::new(heuristic)),
+            ..Self::default()
+        }
+    }
+
+    /// Directions in which moves are tried, also used to break ties between
+    /// nodes of equal cost.
+    pub fn order(&self) -> [Dir; 4] {
+        self.order
+    }
+
+    pub fn heuristic(&self) -> Option<&Arc<dyn Heuristic>> {
+        self.heuristic.as_ref()
+    }
+
+    pub fn limits(&self) -> &SearchLimits {
+        &self.limits
+    }
+
+    /// Token checked by the search, clones of the configuration share it.
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+
+    /// Checks that the heuristic, if any, can estimate boards of `shape`.
+    pub fn check_shape(&self, shape: Shape) -> Result<(), ConfigError> {
+        match &self.heuristic {
+            Some(heuristic) if !heuristic.supports_shape(shape) => {
+                Err(ConfigError::UnsupportedShape(shape))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the heuristic, if any, can estimate the distance to `goal`.
+    pub fn check_goal(&self, goal: &Goal) -> Result<(), ConfigError> {
+        self.check_shape(goal.shape())?;
+
+        match &self.heuristic {
+            Some(heuristic) if !heuristic.supports_goal(goal) => Err(ConfigError::UnsupportedGoal),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for SearchConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for dir in self.order {
+            write!(f, "{}", dir)?;
+        }
+
+        if let Some(heuristic) = &self.heuristic {
+            write!(f, "-{}", heuristic.name())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct SearchConfigBuilder {
+    config: SearchConfig,
+}
+
+impl SearchConfigBuilder {
+    /// Sets the move order, every direction must appear exactly once.
+    pub fn order(mut self, order: [Dir; 4]) -> Self {
+        self.config.order = order;
+        self
+    }
+
+    pub fn heuristic(self, heuristic: impl Heuristic + 'static) -> Self {
+        self.shared_heuristic(Arc::new(heuristic))
+    }
+
+    /// Sets a heuristic that is shared with other configurations.
+    pub fn shared_heuristic(mut self, heuristic: Arc<dyn Heuristic>) -> Self {
+        self.config.heuristic = Some(heuristic);
+        self
+    }
+
+    /// Replaces all limits at once.
+    pub fn limits(mut self, limits: SearchLimits) -> Self {
+        self.config.limits = limits;
+        self
+    }
+
+    pub fn max_depth(mut self, depth: u16) -> Self {
+        self.config.limits.max_depth = Some(depth);
+        self
+    }
+
+    pub fn max_nodes(mut self, nodes: usize) -> Self {
+        self.config.limits.max_nodes = Some(nodes);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.limits.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the approximate memory ceiling in bytes.
+    pub fn max_memory(mut self, bytes: usize) -> Self {
+        self.config.limits.max_memory = Some(bytes);
+        self
+    }
+
+    /// Lets the search be stopped by calling `cancel` on `token`.
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.config.cancel = token;
+        self
+    }
+
+    pub fn build(self) -> Result<SearchConfig, ConfigError> {
+        let order = self.config.order;
+
+        if Dir::values().iter().any(|dir| !order.contains(dir)) {
+            return Err(ConfigError::InvalidOrder(order));
+        }
+
+        Ok(self.config)
+    }
+}
+
+/// Reason why a configuration cannot be used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigError {
+    /// The move order does not contain every direction exactly once.
+    InvalidOrder([Dir; 4]),
+    /// An informed algorithm was configured without a heuristic.
+    MissingHeuristic,
+    /// An uninformed algorithm was configured with a heuristic it would ignore.
+    UnusedHeuristic,
+    /// Weighted A* needs a finite, positive weight.
+    InvalidWeight(f64),
+    /// The heuristic cannot estimate boards of this shape.
+    UnsupportedShape(Shape),
+    /// The heuristic cannot estimate the distance to this goal.
+    UnsupportedGoal,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::InvalidOrder(order) => write!(
+                f,
+                "Move order {}{}{}{} must contain every direction exactly once",
+                order[0], order[1], order[2], order[3]
+            ),
+            ConfigError::MissingHeuristic => write!(f, "This algorithm requires a heuristic"),
+            ConfigError::UnusedHeuristic => {
+                write!(f, "This algorithm does not use a heuristic")
+            }
+            ConfigError::InvalidWeight(weight) => {
+                write!(f, "Invalid weight {}, expected a positive number", weight)
+            }
+            ConfigError::UnsupportedShape(shape) => {
+                write!(f, "The heuristic does not support {} boards", shape)
+            }
+            ConfigError::UnsupportedGoal => {
+                write!(f, "The heuristic does not support this goal")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ConfigError, SearchConfig};
+    use crate::core::{Dir, SearchLimits};
+    use crate::heuristics::Manhattan;
+
+    #[test]
+    fn test_default_config() {
+        let config = SearchConfig::builder().build().unwrap();
+
+        assert_eq!(config.order(), Dir::values());
+        assert!(config.heuristic().is_none());
+        assert_eq!(*config.limits(), SearchLimits::default());
+    }
+
+    #[test]
+    fn test_builder_sets_fields_independently() {
+        let order = [Dir::Left, Dir::Up, Dir::Down, Dir::Right];
+        let config = SearchConfig::builder()
+            .max_depth(12)
+            .timeout(Duration::from_secs(1))
+            .heuristic(Manhattan)
+            .order(order)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.order(), order);
+        assert_eq!(config.heuristic().unwrap().name(), "manh");
+        assert_eq!(config.limits().max_depth, Some(12));
+        assert_eq!(config.limits().timeout, Some(Duration::from_secs(1)));
+        assert_eq!(config.limits().max_nodes, None);
+        assert_eq!(config.to_string(), "LUDR-manh");
+    }
+
+    #[test]
+    fn test_duplicate_direction_is_rejected() {
+        let order = [Dir::Left, Dir::Left, Dir::Down, Dir::Right];
+        let result = SearchConfig::builder().order(order).build();
+
+        assert_eq!(result.err(), Some(ConfigError::InvalidOrder(order)));
+    }
+}
@@ -0,0 +1,95 @@
+use std::{error, fmt};
+
+use super::{ConfigError, Limit, Solution};
+
+/// Reason why `Solver::solve` did not return a solution.
+///
+/// `InvalidBoard`, `Unsolvable` and `UnsupportedConfig` are reported before
+/// searching. The other variants mean the search gave up, and carry the
+/// statistics gathered until then, with an empty path.
+#[derive(Debug, Clone)]
+pub enum SolveError {
+    /// The board is not a valid puzzle state.
+    InvalidBoard(String),
+    /// The solved board cannot be reached from the given one.
+    Unsolvable,
+    /// Every state within the depth limit was searched.
+    DepthLimitReached(Box<Solution>),
+    /// The limit of expanded nodes was reached.
+    NodeLimitReached(Box<Solution>),
+    /// The time limit was reached.
+    Timeout(Box<Solution>),
+    /// The stored nodes would exceed the memory limit.
+    MemoryLimitReached(Box<Solution>),
+    /// The search was stopped through its `CancelToken`.
+    Cancelled(Box<Solution>),
+    /// The configuration cannot be used for this problem.
+    UnsupportedConfig(ConfigError),
+}
+
+impl SolveError {
+    /// Statistics of the search, if it was started.
+    pub fn stats(&self) -> Option<&Solution> {
+        match self {
+            SolveError::DepthLimitReached(stats)
+            | SolveError::NodeLimitReached(stats)
+            | SolveError::Timeout(stats)
+            | SolveError::MemoryLimitReached(stats)
+            | SolveError::Cancelled(stats) => Some(stats),
+            _ => None,
+        }
+    }
+
+    /// Limit which stopped the search, if any.
+    pub fn limit(&self) -> Option<Limit> {
+        match self {
+            SolveError::DepthLimitReached(_) => Some(Limit::Depth),
+            SolveError::NodeLimitReached(_) => Some(Limit::Nodes),
+            SolveError::Timeout(_) => Some(Limit::Time),
+            SolveError::MemoryLimitReached(_) => Some(Limit::Memory),
+            SolveError::Cancelled(_) => Some(Limit::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Whether the search gave up or was cancelled on a puzzle that has a solution.
+    pub fn is_limit(&self) -> bool {
+        self.stats().is_some()
+    }
+}
+
+impl From<ConfigError> for SolveError {
+    fn from(err: ConfigError) -> Self {
+        SolveError::UnsupportedConfig(err)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::InvalidBoard(reason) => write!(f, "Invalid board: {}", reason),
+            SolveError::Unsolvable => write!(f, "Puzzle is unsolvable"),
+            SolveError::DepthLimitReached(_) => {
+                write!(f, "Depth limit reached before finding a solution")
+            }
+            SolveError::NodeLimitReached(_) => {
+                write!(f, "Node limit reached before finding a solution")
+            }
+            SolveError::Timeout(_) => write!(f, "Time limit reached before finding a solution"),
+            SolveError::MemoryLimitReached(_) => {
+                write!(f, "Memory limit reached before finding a solution")
+            }
+            SolveError::Cancelled(_) => write!(f, "Search was cancelled"),
+            SolveError::UnsupportedConfig(err) => write!(f, "Unsupported configuration: {}", err),
+        }
+    }
+}
+
+impl error::Error for SolveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SolveError::UnsupportedConfig(err) => Some(err),
+            _ => None,
+        }
+    }
+}
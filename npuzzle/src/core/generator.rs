@@ -0,0 +1,378 @@
+use std::{collections::HashSet, error, fmt, sync::Arc};
+
+use super::{
+    Dir, Goal, Problem, SearchConfig, SearchLimits, SolveError, Tile, node::Node,
+    solver::IdaStarSolver,
+};
+use crate::heuristics::{Heuristic, LinearConflict, WalkingDistance};
+
+/// Default number of states the backward breadth-first search may keep
+/// before boards at an exact distance are looked for by a verified walk.
+pub const LAYER_LIMIT: usize = 1 << 20;
+
+// Attempts of the verified walk before giving up on a distance
+const WALK_ATTEMPTS: usize = 64;
+
+/// Seeded source of random puzzles, the same seed always yields the same
+/// sequence of boards.
+pub struct Generator {
+    rng: SplitMix,
+    layer_limit: usize,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix(seed),
+            layer_limit: LAYER_LIMIT,
+        }
+    }
+
+    /// Sets how many states the backward breadth-first search of `at_distance`
+    /// may keep, `LAYER_LIMIT` by default.
+    ///
+    /// At least the first layer is always expanded, so a limit of 0 acts as 1
+    /// and the verified walk starts at least one move from the goal.
+    pub fn layer_limit(mut self, limit: usize) -> Self {
+        self.layer_limit = limit.max(1);
+        self
+    }
+
+    /// Uniformly random board from which `goal` can be reached.
+    ///
+    /// # Behavior
+    /// Shuffles the tiles of the goal. Half of all permutations cannot reach
+    /// the goal, those get their first two tiles other than the blank swapped.
+    /// The swap flips the parity and pairs every unsolvable board with exactly
+    /// one solvable board, so every solvable board is equally likely.
+    ///
+    /// On a single row or column the tiles cannot pass each other, the only
+    /// solvable boards hold them in goal order, so only the blank is placed
+    /// at random.
+    pub fn random(&mut self, goal: &Goal) -> Problem {
+        let shape = goal.shape();
+        if shape.height == 1 || shape.width == 1 {
+            let mut tiles: Vec<Tile> = goal.tiles().iter().copied().filter(|&t| t != 0).collect();
+            tiles.insert(self.rng.below(shape.cells()), 0);
+            return to_problem(&tiles, goal);
+        }
+
+        let mut tiles = goal.tiles().to_vec();
+        for i in (1..tiles.len()).rev() {
+            tiles.swap(i, self.rng.below(i + 1));
+        }
+
+        let mut problem = to_problem(&tiles, goal);
+        if !problem.is_solvable() {
+            let mut numbered = (0..tiles.len()).filter(|&i| tiles[i] != 0);
+            if let (Some(a), Some(b)) = (numbered.next(), numbered.next()) {
+                tiles.swap(a, b);
+            }
+            problem = to_problem(&tiles, goal);
+        }
+
+        problem
+    }
+
+    /// Random board exactly `distance` moves away from `goal` when solved
+    /// optimally.
+    ///
+    /// # Arguments
+    /// * `goal` - The state the board is solved towards
+    /// * `distance` - Length of an optimal solution of the board
+    ///
+    /// # Returns
+    /// * `Ok(Problem)` with a board at that distance
+    /// * `Err(GenerateError::InvalidGoal)` if `goal` is not a valid board
+    /// * `Err(GenerateError::Unreachable)` if no board is that far from the goal
+    /// * `Err(GenerateError::NotFound)` if every walk ended before that distance
+    ///
+    /// # Behavior
+    /// 1. Expands a breadth-first search backwards from the goal, layer by layer
+    /// 2. If layer `distance` is reached within the layer limit, draws a
+    ///    board from it uniformly
+    /// 3. Otherwise walks on from a random board of the deepest layer, keeping
+    ///    a random move only if an optimal IDA* search confirms it leads one
+    ///    move further from the goal, and starts over from dead ends
+    pub fn at_distance(&mut self, goal: &Goal, distance: u16) -> Result<Problem, GenerateError> {
+        let start = Node::new(goal.table()).map_err(GenerateError::InvalidGoal)?;
+        let mut visited = HashSet::from([start.key().clone()]);
+        let mut layer = vec![start];
+        let mut depth = 0;
+
+        while depth < distance && visited.len() <= self.layer_limit {
+            let next: Vec<Node> = layer
+                .iter()
+                .flat_map(|node| node.get_child_nodes(Dir::values()))
+                .filter(|child| visited.insert(child.key().clone()))
+                .collect();
+
+            if next.is_empty() {
+                return Err(GenerateError::Unreachable { farthest: depth });
+            }
+
+            layer = next;
+            depth += 1;
+        }
+
+        let from = layer[self.rng.below(layer.len())].clone();
+        if depth == distance {
+            return Ok(to_problem(&from.get_board(), goal));
+        }
+
+        drop(visited);
+        for _ in 0..WALK_ATTEMPTS {
+            if let Some(node) = self.walk(goal, from.clone(), depth, distance) {
+                return Ok(to_problem(&node.get_board(), goal));
+            }
+        }
+
+        Err(GenerateError::NotFound)
+    }
+
+    // Walks from `node`, `depth` moves from the goal, to a board `distance`
+    // moves away, `None` at a board all of whose neighbours are closer
+    fn walk(&mut self, goal: &Goal, mut node: Node, mut depth: u16, distance: u16) -> Option<Node> {
+        let heuristic: Arc<dyn Heuristic> =
+            if WalkingDistance::default().supports_shape(goal.shape()) {
+                Arc::new(WalkingDistance::default())
+            } else {
+                Arc::new(LinearConflict)
+            };
+
+        while depth < distance {
+            // A move changes the distance by exactly one, so a neighbour is
+            // further away unless it is solved within `depth - 1` moves
+            let limits = SearchLimits {
+                max_depth: Some(depth - 1),
+                ..SearchLimits::default()
+            };
+            let config = SearchConfig::builder()
+                .shared_heuristic(heuristic.clone())
+                .limits(limits)
+                .build()
+                .expect("Default move order is valid");
+            let mut solver = IdaStarSolver::new(config).expect("IDA* is given a heuristic");
+
+            let mut dirs = Dir::values();
+            for i in (1..dirs.len()).rev() {
+                dirs.swap(i, self.rng.below(i + 1));
+            }
+
+            node = node.get_child_nodes(dirs).into_iter().find(|child| {
+                matches!(
+                    solver.solve(to_problem(&child.get_board(), goal)),
+                    Err(SolveError::DepthLimitReached(_))
+                )
+            })?;
+            depth += 1;
+        }
+
+        Some(node)
+    }
+}
+
+/// Reason why `Generator::at_distance` did not return a board.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateError {
+    /// The goal is not a valid puzzle state.
+    InvalidGoal(String),
+    /// No board is that far from the goal, the farthest are `farthest` moves away.
+    Unreachable { farthest: u16 },
+    /// Every walk ran into a board with no neighbour further from the goal.
+    NotFound,
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerateError::InvalidGoal(reason) => write!(f, "Invalid goal: {}", reason),
+            GenerateError::Unreachable { farthest } => write!(
+                f,
+                "No board is that far from the goal, the farthest are {} moves away",
+                farthest
+            ),
+            GenerateError::NotFound => write!(f, "No board that far from the goal was found"),
+        }
+    }
+}
+
+impl error::Error for GenerateError {}
+
+fn to_problem(tiles: &[Tile], goal: &Goal) -> Problem {
+    let table = tiles
+        .chunks(goal.shape().width as usize)
+        .map(|row| row.to_vec())
+        .collect();
+
+    Problem::with_goal(table, goal.clone())
+}
+
+// SplitMix64, small and fast with good statistical quality
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform number below `bound`, rejecting the values that would make
+    // smaller results more likely
+    fn below(&mut self, bound: usize) -> usize {
+        let bound = bound as u64;
+        let zone = u64::MAX - u64::MAX % bound;
+
+        loop {
+            let value = self.next();
+            if value < zone {
+                return (value % bound) as usize;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{GenerateError, Generator};
+    use crate::core::{Goal, Problem, SearchConfig, Shape, solver::AStarSolver};
+    use crate::heuristics::Manhattan;
+
+    fn optimal_distance(problem: Problem) -> u16 {
+        AStarSolver::new(SearchConfig::informed(Manhattan))
+            .unwrap()
+            .solve(problem)
+            .unwrap()
+            .result_len
+    }
+
+    // Test if the same seed yields the same boards
+    #[test]
+    fn test_seed_is_reproducible() {
+        let goal = Goal::standard(Shape::square(4));
+        let (mut a, mut b) = (Generator::new(7), Generator::new(7));
+
+        for _ in 0..10 {
+            assert_eq!(a.random(&goal).get(), b.random(&goal).get());
+        }
+        assert_ne!(
+            Generator::new(7).random(&goal).get(),
+            Generator::new(8).random(&goal).get()
+        );
+    }
+
+    // Test if every solvable board of a small puzzle is drawn about equally often
+    #[test]
+    fn test_random_boards_are_solvable_and_uniform() {
+        let goal = Goal::standard(Shape::new(2, 3));
+        let mut generator = Generator::new(1);
+        let mut counts = HashMap::new();
+
+        for _ in 0..36_000 {
+            let problem = generator.random(&goal);
+            assert!(problem.is_solvable());
+            *counts.entry(problem.get()).or_insert(0) += 1;
+        }
+
+        // 360 solvable boards, each expected 100 times
+        assert_eq!(counts.len(), 360);
+        assert!(counts.values().all(|&count| (50..=150).contains(&count)));
+    }
+
+    // Test if boards of any size and goal are solvable
+    #[test]
+    fn test_random_large_and_custom_goals() {
+        let mut generator = Generator::new(3);
+
+        for goal in [
+            Goal::standard(Shape::square(20)),
+            Goal::spiral(Shape::new(5, 7)),
+            Goal::blank_first(Shape::square(4)),
+        ] {
+            for _ in 0..5 {
+                let problem = generator.random(&goal);
+                assert_eq!(problem.goal(), goal);
+                assert!(problem.is_solvable());
+            }
+        }
+    }
+
+    // Test if boards drawn from a breadth-first layer are exactly that far
+    #[test]
+    fn test_exact_distance_from_layer() {
+        let goal = Goal::standard(Shape::square(3));
+        let mut generator = Generator::new(5);
+
+        for distance in [0, 1, 6, 13, 20] {
+            let problem = generator.at_distance(&goal, distance).unwrap();
+            assert_eq!(optimal_distance(problem), distance);
+        }
+    }
+
+    // Test if boards found by the verified walk are exactly that far
+    #[test]
+    fn test_exact_distance_by_walk() {
+        let mut generator = Generator::new(11).layer_limit(50);
+
+        for goal in [
+            Goal::standard(Shape::square(3)),
+            Goal::spiral(Shape::square(3)),
+        ] {
+            for distance in [9, 16] {
+                let problem = generator.at_distance(&goal, distance).unwrap();
+                assert_eq!(optimal_distance(problem), distance);
+            }
+        }
+    }
+
+    // Test if a distance beyond the farthest board is reported
+    #[test]
+    fn test_unreachable_distance() {
+        let goal = Goal::standard(Shape::square(2));
+        let mut generator = Generator::new(0);
+
+        assert!(generator.at_distance(&goal, 6).is_ok());
+        assert_eq!(
+            generator.at_distance(&goal, 7).unwrap_err(),
+            GenerateError::Unreachable { farthest: 6 }
+        );
+    }
+
+    // Test if the walk starts after the first layer when no layer may be kept
+    #[test]
+    fn test_walk_without_layers() {
+        let goal = Goal::standard(Shape::square(3));
+        let mut generator = Generator::new(1).layer_limit(0);
+
+        for distance in [0, 1, 5] {
+            let problem = generator.at_distance(&goal, distance).unwrap();
+            assert_eq!(optimal_distance(problem), distance);
+        }
+    }
+
+    // Test if boards of a single row or column keep the goal order of tiles
+    #[test]
+    fn test_random_single_line() {
+        let mut generator = Generator::new(2);
+
+        for goal in [
+            Goal::standard(Shape::new(1, 4)),
+            Goal::standard(Shape::new(4, 1)),
+        ] {
+            let mut boards = HashMap::new();
+            for _ in 0..100 {
+                let problem = generator.random(&goal);
+                assert!(problem.is_solvable());
+                *boards.entry(problem.get()).or_insert(0) += 1;
+            }
+
+            // The blank is in each of the 4 places
+            assert_eq!(boards.len(), 4);
+        }
+    }
+}
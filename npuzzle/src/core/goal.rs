@@ -0,0 +1,173 @@
+use super::{PackedBoard, ParseError, ParseErrorKind, Shape, Tile, input::parse_board, node::Node};
+
+/// The state a puzzle is solved towards.
+///
+/// Besides the tiles of the goal board it keeps the goal position of every
+/// tile, so heuristics look up where a tile belongs instead of deriving it
+/// from the tile number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Goal {
+    shape: Shape,
+    tiles: Vec<Tile>,
+    // Index of every tile on the goal board, the blank first
+    positions: Vec<usize>,
+    key: PackedBoard,
+}
+
+impl Goal {
+    /// Goal given as a table of rows, holding every tile from 0 to N exactly once.
+    pub fn new(table: Vec<Vec<Tile>>) -> Result<Self, String> {
+        let node = Node::new(table)?;
+        Ok(Self::from_tiles(node.shape(), node.get_board()))
+    }
+
+    /// Goal read from a file in any of the input formats, see `input::parse_board`.
+    pub fn from_string(s: &str) -> Result<Self, ParseError> {
+        let table = parse_board(s)?;
+        Self::new(table)
+            .map_err(|reason| ParseError::new(1, 1, ParseErrorKind::InvalidBoard(reason)))
+    }
+
+    /// Tiles in order with the blank in the bottom right corner.
+    pub fn standard(shape: Shape) -> Self {
+        let tiles = (1..shape.cells()).chain([0]).map(|tile| tile as Tile);
+        Self::from_tiles(shape, tiles.collect())
+    }
+
+    /// Blank in the top left corner followed by the tiles in order.
+    pub fn blank_first(shape: Shape) -> Self {
+        let tiles = (0..shape.cells()).map(|tile| tile as Tile);
+        Self::from_tiles(shape, tiles.collect())
+    }
+
+    /// Tiles in order along a clockwise spiral from the top left corner
+    /// inwards, the blank in the last cell of the spiral.
+    pub fn spiral(shape: Shape) -> Self {
+        let (height, width) = (shape.height as usize, shape.width as usize);
+        let mut tiles = vec![0; shape.cells()];
+        let (mut top, mut bottom, mut left, mut right) = (0, height, 0, width);
+        let mut cells = Vec::with_capacity(shape.cells());
+
+        while top < bottom && left < right {
+            cells.extend((left..right).map(|col| top * width + col));
+            cells.extend((top + 1..bottom).map(|row| row * width + right - 1));
+            if top + 1 < bottom {
+                cells.extend(
+                    (left..right - 1)
+                        .rev()
+                        .map(|col| (bottom - 1) * width + col),
+                );
+            }
+            if left + 1 < right {
+                cells.extend((top + 1..bottom - 1).rev().map(|row| row * width + left));
+            }
+            (top, bottom, left, right) = (top + 1, bottom - 1, left + 1, right - 1);
+        }
+
+        // The last cell keeps the blank
+        for (tile, &cell) in cells.iter().enumerate().take(shape.cells() - 1) {
+            tiles[cell] = tile as Tile + 1;
+        }
+
+        Self::from_tiles(shape, tiles)
+    }
+
+    fn from_tiles(shape: Shape, tiles: Vec<Tile>) -> Self {
+        let mut positions = vec![0; tiles.len()];
+        for (index, &tile) in tiles.iter().enumerate() {
+            positions[tile as usize] = index;
+        }
+
+        Self {
+            shape,
+            key: PackedBoard::pack(&tiles),
+            tiles,
+            positions,
+        }
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    /// Tiles of the goal board, row by row.
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Goal board as a table of rows, as taken by `Node::new`.
+    pub fn table(&self) -> Vec<Vec<Tile>> {
+        self.tiles
+            .chunks(self.shape.width as usize)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Index `tile` has on the goal board.
+    pub fn position(&self, tile: Tile) -> usize {
+        self.positions[tile as usize]
+    }
+
+    /// Exact key of the goal board, see `Node::key`.
+    pub fn key(&self) -> &PackedBoard {
+        &self.key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Goal;
+    use crate::core::{ParseErrorKind, Shape};
+
+    #[test]
+    fn test_standard_and_blank_first() {
+        let shape = Shape::new(2, 3);
+
+        assert_eq!(Goal::standard(shape).tiles(), [1, 2, 3, 4, 5, 0]);
+        assert_eq!(Goal::blank_first(shape).tiles(), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(Goal::standard(shape).position(0), 5);
+        assert_eq!(Goal::blank_first(shape).position(4), 4);
+    }
+
+    #[test]
+    fn test_spiral() {
+        assert_eq!(
+            Goal::spiral(Shape::square(4)).table(),
+            vec![
+                vec![1, 2, 3, 4],
+                vec![12, 13, 14, 5],
+                vec![11, 0, 15, 6],
+                vec![10, 9, 8, 7],
+            ]
+        );
+        assert_eq!(
+            Goal::spiral(Shape::new(3, 4)).table(),
+            vec![vec![1, 2, 3, 4], vec![10, 11, 0, 5], vec![9, 8, 7, 6]]
+        );
+        assert_eq!(
+            Goal::spiral(Shape::new(4, 2)).table(),
+            vec![vec![1, 2], vec![0, 3], vec![7, 4], vec![6, 5]]
+        );
+    }
+
+    #[test]
+    fn test_custom_goal() {
+        let goal = Goal::new(vec![vec![3, 1, 2], vec![0, 5, 4]]).unwrap();
+
+        assert_eq!(goal.shape(), Shape::new(2, 3));
+        assert_eq!(goal.position(3), 0);
+        assert_eq!(goal.position(0), 3);
+        assert!(Goal::new(vec![vec![1, 1], vec![2, 0]]).is_err());
+        assert_eq!(Goal::from_string("2 3\n3 1 2\n0 5 4\n").unwrap(), goal);
+        assert!(Goal::from_string("2 2\n1 1\n2 0\n").is_err());
+    }
+
+    // Test if a goal too large to be stored is reported instead of panicking
+    #[test]
+    fn test_goal_too_large() {
+        let rows: Vec<String> = (0..256).map(|tile| format!("[{}]", tile)).collect();
+        let err = Goal::from_string(&format!("[{}]", rows.join(", "))).unwrap_err();
+
+        assert_eq!(err.kind, ParseErrorKind::TooLarge(256));
+    }
+}
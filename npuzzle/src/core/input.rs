@@ -0,0 +1,937 @@
+use std::{error, fmt, iter::Peekable, str::CharIndices};
+
+use super::Tile;
+
+/// Layout of the puzzles in an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A header line `rows cols` followed by one line of tiles per row.
+    Grid,
+    /// Header and tiles on one line, split by a bar, `3 3 | 1 2 3 4 5 6 7 0 8`.
+    Line,
+    /// Rows as JSON arrays of tiles, `[[1, 2, 3], [4, 5, 6], [7, 0, 8]]`.
+    Json,
+}
+
+impl Format {
+    /// Format of `s`, JSON if it starts with `[`, single line if its first
+    /// line holds a `|`, and grid otherwise.
+    pub fn detect(s: &str) -> Format {
+        let first = s.lines().find(|line| !line.trim().is_empty());
+
+        match first.unwrap_or_default() {
+            line if line.trim_start().starts_with('[') => Format::Json,
+            line if line.contains('|') => Format::Line,
+            _ => Format::Grid,
+        }
+    }
+}
+
+/// Reads a board, in the format detected by `Format::detect`.
+///
+/// # Arguments
+/// * `s` - Content of the file, blank lines are skipped
+///
+/// # Returns
+/// * `Ok` with the rows of the board
+/// * `Err` with the line and column of the first problem found
+pub fn parse_board(s: &str) -> Result<Vec<Vec<Tile>>, ParseError> {
+    parse_board_as(s, Format::detect(s))
+}
+
+/// Reads a file holding exactly one board in `format`.
+///
+/// # Behavior
+/// 1. Reads the board, in the grid format the header must hold two positive
+///    numbers, every row must be as wide as the header declares and there
+///    must be exactly as many rows as declared
+/// 2. Checks that no tile appears twice and the blank is present
+/// 3. Checks that every tile is below the number of cells, which together
+///    with the previous checks makes every tile appear exactly once
+pub fn parse_board_as(s: &str, format: Format) -> Result<Vec<Vec<Tile>>, ParseError> {
+    let mut boards = read(s, format, true)?;
+    boards.remove(0).check()
+}
+
+/// Reads every board of a batch file in `format`, grids one after another,
+/// one board per line, or JSON boards and arrays of boards.
+///
+/// # Returns
+/// * `Ok` with the rows of every board, in the order of the file
+/// * `Err` with the line and column of the first problem found
+pub fn parse_boards(s: &str, format: Format) -> Result<Vec<Vec<Vec<Tile>>>, ParseError> {
+    read(s, format, false)?
+        .into_iter()
+        .map(Board::check)
+        .collect()
+}
+
+// Board as read, every tile with the line and column it was read from
+struct Board {
+    cols: usize,
+    tiles: Vec<(Tile, usize, usize)>,
+    // Location of the start of the board and line of its end
+    start: (usize, usize),
+    last_line: usize,
+}
+
+impl Board {
+    fn check(self) -> Result<Vec<Vec<Tile>>, ParseError> {
+        check_tiles(&self.tiles, self.last_line)?;
+
+        Ok(self
+            .tiles
+            .chunks(self.cols)
+            .map(|row| row.iter().map(|&(tile, _, _)| tile).collect())
+            .collect())
+    }
+}
+
+// Boards of `s`, just the first one if `single`, reporting anything after it
+fn read(s: &str, format: Format, single: bool) -> Result<Vec<Board>, ParseError> {
+    let lines: Vec<(usize, &str)> = s
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+
+    let boards = match format {
+        Format::Grid => {
+            let mut next = 0;
+            let mut boards = Vec::new();
+            while next < lines.len() {
+                let board = read_grid(&lines, &mut next)?;
+                if let (true, Some(&(line, _))) = (single, lines.get(next)) {
+                    let expected = board.tiles.len() / board.cols;
+                    return Err(ParseError::new(
+                        line,
+                        1,
+                        ParseErrorKind::TooManyRows { expected },
+                    ));
+                }
+                boards.push(board);
+            }
+            boards
+        }
+        Format::Line => lines
+            .iter()
+            .map(|&(line_number, line)| read_line(line_number, line))
+            .collect::<Result<_, _>>()?,
+        Format::Json => read_json(s)?,
+    };
+
+    match boards.get(1) {
+        None if boards.is_empty() => Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader)),
+        Some(second) if single => Err(ParseError::new(
+            second.start.0,
+            second.start.1,
+            ParseErrorKind::MultiplePuzzles,
+        )),
+        _ => Ok(boards),
+    }
+}
+
+// Reads a header line and the rows it declares from `lines[*next..]`
+fn read_grid(lines: &[(usize, &str)], next: &mut usize) -> Result<Board, ParseError> {
+    let (header_line, header) = lines[*next];
+    let (rows, cols) = parse_header(header_line, &tokens(header))?;
+    *next += 1;
+
+    let mut tiles: Vec<(Tile, usize, usize)> = Vec::with_capacity(rows * cols);
+    let mut last_line = header_line;
+    for row in 0..rows {
+        let Some(&(line_number, line)) = lines.get(*next) else {
+            return Err(ParseError::new(
+                last_line + 1,
+                1,
+                ParseErrorKind::RowCount {
+                    expected: rows,
+                    found: row,
+                },
+            ));
+        };
+
+        let fields = tokens(line);
+        if fields.len() != cols {
+            let column = fields
+                .get(cols)
+                .map_or(end_column(line), |&(column, _)| column);
+            return Err(ParseError::new(
+                line_number,
+                column,
+                ParseErrorKind::RowLength {
+                    expected: cols,
+                    found: fields.len(),
+                },
+            ));
+        }
+
+        tiles.extend(parse_tiles(line_number, &fields)?);
+        last_line = line_number;
+        *next += 1;
+    }
+
+    Ok(Board {
+        cols,
+        tiles,
+        start: (header_line, 1),
+        last_line,
+    })
+}
+
+// Reads a board written as `rows cols | tiles`
+fn read_line(line_number: usize, line: &str) -> Result<Board, ParseError> {
+    let fields = tokens(line);
+    let Some(bar) = fields.iter().position(|&(_, token)| token == "|") else {
+        let column = fields
+            .get(2)
+            .map_or(end_column(line), |&(column, _)| column);
+        return Err(ParseError::new(
+            line_number,
+            column,
+            ParseErrorKind::MissingSeparator,
+        ));
+    };
+
+    let (rows, cols) = parse_header(line_number, &fields[..bar])?;
+    let fields = &fields[bar + 1..];
+    if fields.len() != rows * cols {
+        let column = fields
+            .get(rows * cols)
+            .map_or(end_column(line), |&(column, _)| column);
+        return Err(ParseError::new(
+            line_number,
+            column,
+            ParseErrorKind::TileCount {
+                expected: rows * cols,
+                found: fields.len(),
+            },
+        ));
+    }
+
+    Ok(Board {
+        cols,
+        tiles: parse_tiles(line_number, fields)?,
+        start: (line_number, 1),
+        last_line: line_number,
+    })
+}
+
+fn parse_header(
+    line_number: usize,
+    fields: &[(usize, &str)],
+) -> Result<(usize, usize), ParseError> {
+    let invalid = |column| {
+        let header: Vec<&str> = fields.iter().map(|&(_, token)| token).collect();
+        ParseError::new(
+            line_number,
+            column,
+            ParseErrorKind::InvalidHeader(header.join(" ")),
+        )
+    };
+
+    if fields.len() != 2 {
+        return Err(invalid(fields.get(2).map_or(1, |&(column, _)| column)));
+    }
+
+    let size = |&(column, token): &(usize, &str)| {
+        token
+            .parse::<u8>()
+            .ok()
+            .filter(|&size| size > 0)
+            .map(usize::from)
+            .ok_or_else(|| invalid(column))
+    };
+
+    Ok((size(&fields[0])?, size(&fields[1])?))
+}
+
+fn parse_tiles(
+    line_number: usize,
+    fields: &[(usize, &str)],
+) -> Result<Vec<(Tile, usize, usize)>, ParseError> {
+    fields
+        .iter()
+        .map(|&(column, token)| parse_tile(line_number, column, token))
+        .collect()
+}
+
+fn parse_tile(line: usize, column: usize, token: &str) -> Result<(Tile, usize, usize), ParseError> {
+    match token.parse::<Tile>() {
+        Ok(tile) => Ok((tile, line, column)),
+        Err(_) => Err(ParseError::new(
+            line,
+            column,
+            ParseErrorKind::InvalidToken(token.to_string()),
+        )),
+    }
+}
+
+// Reports tiles that repeat, a missing blank and tiles beyond the board, in
+// that order
+fn check_tiles(tiles: &[(Tile, usize, usize)], last_line: usize) -> Result<(), ParseError> {
+    let mut seen: Vec<Option<(usize, usize)>> = vec![None; tiles.len()];
+
+    for &(tile, line, column) in tiles {
+        let Some(first) = seen.get_mut(tile as usize) else {
+            continue;
+        };
+        if let Some((first_line, first_column)) = *first {
+            return Err(ParseError::new(
+                line,
+                column,
+                ParseErrorKind::DuplicateTile {
+                    tile,
+                    first_line,
+                    first_column,
+                },
+            ));
+        }
+        *first = Some((line, column));
+    }
+
+    if seen[0].is_none() {
+        return Err(ParseError::new(last_line, 1, ParseErrorKind::MissingBlank));
+    }
+
+    match tiles
+        .iter()
+        .find(|&&(tile, _, _)| tile as usize >= tiles.len())
+    {
+        Some(&(tile, line, column)) => Err(ParseError::new(
+            line,
+            column,
+            ParseErrorKind::TileOutOfRange {
+                tile,
+                max: tiles.len() - 1,
+            },
+        )),
+        None => Ok(()),
+    }
+}
+
+// Tokens of `line` split by whitespace, a bar being a token of its own, each
+// with the column it starts at
+fn tokens(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (offset, ch) in line.char_indices().chain([(line.len(), ' ')]) {
+        if ch.is_whitespace() || ch == '|' {
+            if let Some(begin) = start.take() {
+                tokens.push((begin, &line[begin..offset]));
+            }
+            if ch == '|' {
+                tokens.push((offset, "|"));
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+    }
+
+    tokens
+        .into_iter()
+        .map(|(offset, token)| (line[..offset].chars().count() + 1, token))
+        .collect()
+}
+
+// Column just after the last character of `line`
+fn end_column(line: &str) -> usize {
+    line.trim_end().chars().count() + 1
+}
+
+// Boards of a JSON file, each a value holding a board or an array of boards
+fn read_json(s: &str) -> Result<Vec<Board>, ParseError> {
+    let mut reader = JsonReader::new(s);
+    let mut boards = Vec::new();
+
+    while reader.skip_whitespace() {
+        let value = reader.value()?;
+        match &value.kind {
+            JsonKind::Array(items) if items.first().is_some_and(JsonValue::is_board) => {
+                for item in items {
+                    boards.push(json_board(item)?);
+                }
+            }
+            _ => boards.push(json_board(&value)?),
+        }
+    }
+
+    Ok(boards)
+}
+
+fn json_board(value: &JsonValue) -> Result<Board, ParseError> {
+    let rows = match &value.kind {
+        JsonKind::Array(rows) if !rows.is_empty() => rows,
+        _ => return Err(value.error("expected a board, an array of rows")),
+    };
+    // Rows and columns are counted in a byte, like in the header of the other formats
+    if let Some(row) = rows.get(u8::MAX as usize) {
+        return Err(ParseError::new(
+            row.line,
+            row.column,
+            ParseErrorKind::TooLarge(rows.len()),
+        ));
+    }
+
+    let mut cols = None;
+    let mut tiles = Vec::new();
+    for row in rows {
+        let row_tiles = match &row.kind {
+            JsonKind::Array(row_tiles) if !row_tiles.is_empty() => row_tiles,
+            _ => return Err(row.error("expected a row, an array of tiles")),
+        };
+        if let Some(tile) = row_tiles.get(u8::MAX as usize) {
+            return Err(ParseError::new(
+                tile.line,
+                tile.column,
+                ParseErrorKind::TooLarge(row_tiles.len()),
+            ));
+        }
+
+        let expected = *cols.get_or_insert(row_tiles.len());
+        if row_tiles.len() != expected {
+            return Err(ParseError::new(
+                row.line,
+                row.column,
+                ParseErrorKind::RowLength {
+                    expected,
+                    found: row_tiles.len(),
+                },
+            ));
+        }
+
+        for tile in row_tiles {
+            match tile.kind {
+                JsonKind::Number(token) => tiles.push(parse_tile(tile.line, tile.column, token)?),
+                JsonKind::Array(_) => return Err(tile.error("expected a tile")),
+            }
+        }
+    }
+
+    Ok(Board {
+        cols: cols.unwrap_or_default(),
+        tiles,
+        start: (value.line, value.column),
+        last_line: value.last_line,
+    })
+}
+
+// JSON value of an input file, arrays and numbers only, with the location it
+// starts at and the line it ends on
+struct JsonValue<'a> {
+    kind: JsonKind<'a>,
+    line: usize,
+    column: usize,
+    last_line: usize,
+}
+
+enum JsonKind<'a> {
+    Number(&'a str),
+    Array(Vec<JsonValue<'a>>),
+}
+
+impl JsonValue<'_> {
+    // Whether the value is an array of arrays, as a board is
+    fn is_board(&self) -> bool {
+        match &self.kind {
+            JsonKind::Array(items) => matches!(
+                items.first(),
+                Some(JsonValue {
+                    kind: JsonKind::Array(_),
+                    ..
+                })
+            ),
+            JsonKind::Number(_) => false,
+        }
+    }
+
+    fn error(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            self.line,
+            self.column,
+            ParseErrorKind::InvalidJson(expected.to_string()),
+        )
+    }
+}
+
+struct JsonReader<'a> {
+    s: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            s,
+            chars: s.char_indices().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    // Skips whitespace, returns whether anything is left
+    fn skip_whitespace(&mut self) -> bool {
+        while self.chars.peek().is_some_and(|&(_, ch)| ch.is_whitespace()) {
+            self.bump();
+        }
+        self.chars.peek().is_some()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let (_, ch) = self.chars.next()?;
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn unexpected(&mut self, expected: &str) -> ParseError {
+        let found = match self.chars.peek() {
+            Some(&(_, ch)) => format!("'{}'", ch),
+            None => "the end of the file".to_string(),
+        };
+        ParseError::new(
+            self.line,
+            self.column,
+            ParseErrorKind::InvalidJson(format!("expected {}, found {}", expected, found)),
+        )
+    }
+
+    fn value(&mut self) -> Result<JsonValue<'a>, ParseError> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+
+        let kind = match self.chars.peek() {
+            Some(&(_, '[')) => {
+                self.bump();
+                JsonKind::Array(self.items()?)
+            }
+            Some(&(start, ch)) if ch.is_ascii_alphanumeric() || ch == '-' => {
+                let mut end = start;
+                while let Some(&(offset, ch)) = self.chars.peek() {
+                    if !(ch.is_ascii_alphanumeric() || "+-.".contains(ch)) {
+                        break;
+                    }
+                    end = offset + ch.len_utf8();
+                    self.bump();
+                }
+                JsonKind::Number(&self.s[start..end])
+            }
+            _ => return Err(self.unexpected("a tile or '['")),
+        };
+
+        Ok(JsonValue {
+            kind,
+            line,
+            column,
+            last_line: self.line,
+        })
+    }
+
+    // Items of an array up to its closing bracket, the opening one read
+    fn items(&mut self) -> Result<Vec<JsonValue<'a>>, ParseError> {
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.chars.peek().is_some_and(|&(_, ch)| ch == ']') {
+            self.bump();
+            return Ok(items);
+        }
+
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(&(_, ',')) => self.bump(),
+                Some(&(_, ']')) => {
+                    self.bump();
+                    return Ok(items);
+                }
+                _ => return Err(self.unexpected("',' or ']'")),
+            };
+        }
+    }
+}
+
+/// Why an input file could not be read, at the line and column where the
+/// problem was found, both counting from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+/// Reason why an input file could not be read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    /// The file holds nothing but whitespace.
+    MissingHeader,
+    /// The first line is not two positive numbers of rows and columns.
+    InvalidHeader(String),
+    /// A token of a row is not a tile number.
+    InvalidToken(String),
+    /// A row holds a different number of tiles than the header declares.
+    RowLength { expected: usize, found: usize },
+    /// The file holds fewer rows than the header declares.
+    RowCount { expected: usize, found: usize },
+    /// The file holds more rows than the header declares.
+    TooManyRows { expected: usize },
+    /// A single line board has no bar between its header and its tiles.
+    MissingSeparator,
+    /// A single line board holds a different number of tiles than its header declares.
+    TileCount { expected: usize, found: usize },
+    /// JSON input that is not an array of rows of tiles, saying what was expected.
+    InvalidJson(String),
+    /// JSON input with more rows, or a row with more tiles, than supported.
+    TooLarge(usize),
+    /// A board read correctly that cannot be used, saying why.
+    InvalidBoard(String),
+    /// A file read as a single puzzle holds another one.
+    MultiplePuzzles,
+    /// A tile appears a second time, first seen at the given line and column.
+    DuplicateTile {
+        tile: Tile,
+        first_line: usize,
+        first_column: usize,
+    },
+    /// No tile is the blank, 0.
+    MissingBlank,
+    /// A tile is larger than the board has cells.
+    TileOutOfRange { tile: Tile, max: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingHeader => {
+                write!(
+                    f,
+                    "File is empty, expected a header line of rows and columns"
+                )
+            }
+            ParseErrorKind::InvalidHeader(header) => write!(
+                f,
+                "Invalid header '{}', expected rows and columns from 1 to {}",
+                header,
+                u8::MAX
+            ),
+            ParseErrorKind::InvalidToken(token) => write!(
+                f,
+                "Invalid tile '{}', expected a number from 0 to {}",
+                token,
+                Tile::MAX
+            ),
+            ParseErrorKind::RowLength { expected, found } => {
+                write!(f, "Row has {} tiles instead of {}", found, expected)
+            }
+            ParseErrorKind::RowCount { expected, found } => {
+                write!(f, "Found {} rows, the header declares {}", found, expected)
+            }
+            ParseErrorKind::TooManyRows { expected } => {
+                write!(f, "More rows than the {} the header declares", expected)
+            }
+            ParseErrorKind::MissingSeparator => {
+                write!(f, "Expected a board as 'rows cols | tiles'")
+            }
+            ParseErrorKind::TileCount { expected, found } => write!(
+                f,
+                "Board has {} tiles, the header declares {}",
+                found, expected
+            ),
+            ParseErrorKind::InvalidJson(reason) => write!(f, "Invalid JSON, {}", reason),
+            ParseErrorKind::TooLarge(found) => write!(
+                f,
+                "Found {} rows or columns, at most {} are supported",
+                found,
+                u8::MAX
+            ),
+            ParseErrorKind::InvalidBoard(reason) => write!(f, "Invalid board, {}", reason),
+            ParseErrorKind::MultiplePuzzles => {
+                write!(f, "File holds more than one puzzle")
+            }
+            ParseErrorKind::DuplicateTile {
+                tile,
+                first_line,
+                first_column,
+            } => write!(
+                f,
+                "Tile {} appears twice, first at line {}, column {}",
+                tile, first_line, first_column
+            ),
+            ParseErrorKind::MissingBlank => write!(f, "Board has no blank tile (0)"),
+            ParseErrorKind::TileOutOfRange { tile, max } => write!(
+                f,
+                "Tile {} is out of range, the board holds tiles from 0 to {}",
+                tile, max
+            ),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, ParseError, ParseErrorKind, parse_board, parse_board_as, parse_boards};
+
+    fn error(s: &str) -> (usize, usize, ParseErrorKind) {
+        let ParseError { line, column, kind } = parse_board(s).unwrap_err();
+        (line, column, kind)
+    }
+
+    // Test if a valid file is read, skipping blank lines and extra spaces
+    #[test]
+    fn test_valid_board() {
+        let table = parse_board("2 3\n\n 1  2 3\r\n4 0 5\n\n").unwrap();
+
+        assert_eq!(table, vec![vec![1, 2, 3], vec![4, 0, 5]]);
+    }
+
+    // Test if problems with the header are located
+    #[test]
+    fn test_invalid_header() {
+        assert_eq!(error(""), (1, 1, ParseErrorKind::MissingHeader));
+        assert!(matches!(
+            error("3\n1 2 0\n"),
+            (1, 1, ParseErrorKind::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            error("\n3 x\n1 2 0\n"),
+            (2, 3, ParseErrorKind::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            error("0 3\n"),
+            (1, 1, ParseErrorKind::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            error("1 3 3\n1 2 0\n"),
+            (1, 5, ParseErrorKind::InvalidHeader(_))
+        ));
+    }
+
+    // Test if bad tokens, ragged rows and wrong row counts are located
+    #[test]
+    fn test_invalid_rows() {
+        assert_eq!(
+            error("2 2\n1 2\n3 -1\n"),
+            (3, 3, ParseErrorKind::InvalidToken("-1".to_string()))
+        );
+        assert_eq!(
+            error("2 2\n1 2 3\n0\n"),
+            (
+                2,
+                5,
+                ParseErrorKind::RowLength {
+                    expected: 2,
+                    found: 3
+                }
+            )
+        );
+        assert_eq!(
+            error("2 2\n1 2\n0\n"),
+            (
+                3,
+                2,
+                ParseErrorKind::RowLength {
+                    expected: 2,
+                    found: 1
+                }
+            )
+        );
+        assert_eq!(
+            error("2 2\n1 2\n3 0\n4 5\n"),
+            (4, 1, ParseErrorKind::TooManyRows { expected: 2 })
+        );
+        assert_eq!(
+            error("2 2\n1 2\n"),
+            (
+                3,
+                1,
+                ParseErrorKind::RowCount {
+                    expected: 2,
+                    found: 1
+                }
+            )
+        );
+    }
+
+    // Test if repeated, missing and out of range tiles are located
+    #[test]
+    fn test_invalid_tiles() {
+        assert_eq!(
+            error("2 2\n1 2\n0 2\n"),
+            (
+                3,
+                3,
+                ParseErrorKind::DuplicateTile {
+                    tile: 2,
+                    first_line: 2,
+                    first_column: 3
+                }
+            )
+        );
+        assert_eq!(
+            error("2 2\n1 2\n3 4\n"),
+            (3, 1, ParseErrorKind::MissingBlank)
+        );
+        assert_eq!(
+            error("2 2\n1 7\n3 0\n"),
+            (2, 3, ParseErrorKind::TileOutOfRange { tile: 7, max: 3 })
+        );
+    }
+
+    // Test if errors read as English sentences with their location
+    #[test]
+    fn test_messages() {
+        assert_eq!(
+            parse_board("2 2\n1 1\n3 0\n").unwrap_err().to_string(),
+            "Line 2, column 3: Tile 1 appears twice, first at line 2, column 1"
+        );
+    }
+
+    // Test if the format of a file is told from its first line
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(Format::detect("3 3\n1 2 3\n"), Format::Grid);
+        assert_eq!(Format::detect("\n3 3 | 1 2 3 4 5 6 7 0 8\n"), Format::Line);
+        assert_eq!(Format::detect("  [[1, 0]]"), Format::Json);
+        assert_eq!(Format::detect(""), Format::Grid);
+    }
+
+    // Test if the same board is read from every format
+    #[test]
+    fn test_formats() {
+        let table = vec![vec![1, 2, 3], vec![4, 0, 5]];
+
+        assert_eq!(parse_board("2 3 | 1 2 3 4 0 5").unwrap(), table);
+        assert_eq!(parse_board("2 3|1 2 3 4 0 5\n").unwrap(), table);
+        assert_eq!(parse_board("[[1, 2, 3],\n [4, 0, 5]]").unwrap(), table);
+        assert_eq!(parse_board("[[[1,2,3],[4,0,5]]]").unwrap(), table);
+        assert_eq!(
+            parse_board_as("2 3\n1 2 3\n4 0 5", Format::Grid).unwrap(),
+            table
+        );
+    }
+
+    // Test if batch files of every format hold all their boards
+    #[test]
+    fn test_batches() {
+        let first = vec![vec![1, 2], vec![3, 0]];
+        let second = vec![vec![1, 2, 3], vec![4, 0, 5]];
+        let both = vec![first, second];
+
+        let grids = "2 2\n1 2\n3 0\n\n2 3\n1 2 3\n4 0 5\n";
+        assert_eq!(parse_boards(grids, Format::Grid).unwrap(), both);
+
+        let lines = "2 2 | 1 2 3 0\n\n2 3 | 1 2 3 4 0 5\n";
+        assert_eq!(parse_boards(lines, Format::Line).unwrap(), both);
+
+        let array = "[\n  [[1, 2], [3, 0]],\n  [[1, 2, 3], [4, 0, 5]]\n]";
+        assert_eq!(parse_boards(array, Format::Json).unwrap(), both);
+
+        let json_lines = "[[1, 2], [3, 0]]\n[[1, 2, 3], [4, 0, 5]]\n";
+        assert_eq!(parse_boards(json_lines, Format::Json).unwrap(), both);
+
+        // Errors name the line of the board in the batch
+        assert_eq!(
+            parse_boards("2 2 | 1 2 3 0\n2 2 | 1 2 2 0\n", Format::Line)
+                .unwrap_err()
+                .line,
+            2
+        );
+    }
+
+    // Test if problems with single line and JSON boards are located
+    #[test]
+    fn test_invalid_formats() {
+        assert_eq!(
+            error("2 2 | 1 2 3 0\n2 2 | 1 2 3 0\n"),
+            (2, 1, ParseErrorKind::MultiplePuzzles)
+        );
+        assert_eq!(
+            error("2 2 | 1 2 3\n"),
+            (
+                1,
+                12,
+                ParseErrorKind::TileCount {
+                    expected: 4,
+                    found: 3
+                }
+            )
+        );
+        assert_eq!(
+            parse_board_as("2 2 1 2 3 0", Format::Line)
+                .unwrap_err()
+                .kind,
+            ParseErrorKind::MissingSeparator
+        );
+        assert!(matches!(
+            error("[[1, 2],\n [3, 0]"),
+            (2, 8, ParseErrorKind::InvalidJson(_))
+        ));
+        assert!(matches!(
+            error("[[1, 2], [3; 0]]"),
+            (1, 12, ParseErrorKind::InvalidJson(_))
+        ));
+        assert_eq!(
+            error("[[1, 2], [3, -1]]"),
+            (1, 14, ParseErrorKind::InvalidToken("-1".to_string()))
+        );
+        assert_eq!(
+            error("[[1, 2],\n [3, 0, 4]]"),
+            (
+                2,
+                2,
+                ParseErrorKind::RowLength {
+                    expected: 2,
+                    found: 3
+                }
+            )
+        );
+        assert!(matches!(
+            error("[[[1, 2], [3, 0]], [[1, 2], [3, 0]]]"),
+            (1, 20, ParseErrorKind::MultiplePuzzles)
+        ));
+    }
+
+    // Test if JSON boards with more rows or columns than supported are located
+    #[test]
+    fn test_json_too_large() {
+        let rows: Vec<String> = (0..256).map(|tile| format!("[{}]", tile)).collect();
+        assert_eq!(
+            error(&format!("[{}]", rows.join(",\n"))),
+            (256, 1, ParseErrorKind::TooLarge(256))
+        );
+
+        let tiles: Vec<String> = (0..300).map(|tile| tile.to_string()).collect();
+        let json = format!("[[{}]]", tiles.join(", "));
+        let column = json.find(", 255,").unwrap() + 3;
+        assert_eq!(error(&json), (1, column, ParseErrorKind::TooLarge(300)));
+
+        let tiles: Vec<String> = (0..255).map(|tile| tile.to_string()).collect();
+        assert!(parse_board(&format!("[[{}]]", tiles.join(", "))).is_ok());
+    }
+}
@@ -0,0 +1,241 @@
+use std::{
+    fmt,
+    mem::size_of,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use super::{
+    SearchConfig,
+    node::Node,
+    tree::{NodeId, SearchTree},
+};
+
+/// Bounds on the work a single search may do, `None` meaning unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    /// Deepest level the search may reach.
+    pub max_depth: Option<u16>,
+    /// Number of nodes the search may expand.
+    pub max_nodes: Option<usize>,
+    /// Wall-clock time a run may take, counted from the start of the search.
+    pub timeout: Option<Duration>,
+    /// Approximate number of bytes the stored nodes may take.
+    pub max_memory: Option<usize>,
+}
+
+/// Handle for stopping a running search from another thread.
+///
+/// Clones share the same flag, so a token kept by the caller cancels every
+/// search whose `SearchConfig` was built with a clone of it. Once cancelled
+/// the token stays cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Asks every search using this token to stop as soon as it checks it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Limit which stopped a search before it found a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Limit {
+    Depth,
+    Nodes,
+    Time,
+    Memory,
+    /// Not a limit as such, the search was stopped through its `CancelToken`.
+    Cancelled,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Depth => write!(f, "depth"),
+            Limit::Nodes => write!(f, "node"),
+            Limit::Time => write!(f, "time"),
+            Limit::Memory => write!(f, "memory"),
+            Limit::Cancelled => write!(f, "cancellation"),
+        }
+    }
+}
+
+// How often the clock and the cancel token are read, in expanded nodes
+const CHECK_INTERVAL: usize = 256;
+
+/// Tracks the limits during a single run of an algorithm.
+pub(crate) struct Budget {
+    max_depth: u16,
+    max_nodes: usize,
+    deadline: Option<Instant>,
+    max_memory: usize,
+    cancel: CancelToken,
+    node_size: usize,
+    expanded: usize,
+    depth_cut: bool,
+    stopped: Option<Limit>,
+}
+
+impl Budget {
+    /// Starts the clock for a search from `start`.
+    pub(crate) fn new(config: &SearchConfig, start: &Node) -> Self {
+        let limits = config.limits();
+
+        Self {
+            max_depth: limits.max_depth.unwrap_or(u16::MAX),
+            max_nodes: limits.max_nodes.unwrap_or(usize::MAX),
+            deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+            max_memory: limits.max_memory.unwrap_or(usize::MAX),
+            cancel: config.cancel_token().clone(),
+            // A stored node is keyed in the visited set and the frontier, which
+            // also keeps its blank, and linked to its parent in the search tree
+            node_size: 2 * (start.key().memory() + size_of::<NodeId>())
+                + size_of::<u16>()
+                + SearchTree::ENTRY_SIZE,
+            expanded: 0,
+            depth_cut: false,
+            stopped: None,
+        }
+    }
+
+    /// Accounts for the expansion of one node while `stored` nodes are kept
+    /// in memory.
+    ///
+    /// # Returns
+    /// * `false` once any limit other than depth is exceeded or the search was
+    ///   cancelled, the search must stop
+    pub(crate) fn expand(&mut self, stored: usize) -> bool {
+        if self.stopped.is_some() {
+            return false;
+        }
+
+        self.expanded += 1;
+
+        if self.expanded > self.max_nodes {
+            self.stopped = Some(Limit::Nodes);
+        } else if stored.saturating_mul(self.node_size) > self.max_memory {
+            self.stopped = Some(Limit::Memory);
+        } else if self.expanded % CHECK_INTERVAL == 1 {
+            if self.cancel.is_cancelled() {
+                self.stopped = Some(Limit::Cancelled);
+            } else if self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                self.stopped = Some(Limit::Time);
+            }
+        }
+
+        self.stopped.is_none()
+    }
+
+    /// Whether a node at `depth` may be expanded, remembering if it may not.
+    pub(crate) fn within_depth(&mut self, depth: u16) -> bool {
+        if depth >= self.max_depth {
+            self.depth_cut = true;
+            return false;
+        }
+
+        true
+    }
+
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.stopped.is_some()
+    }
+
+    /// Limit that kept the search from finishing, `None` if it searched every
+    /// reachable state.
+    pub(crate) fn stopped_by(&self) -> Option<Limit> {
+        self.stopped.or(self.depth_cut.then_some(Limit::Depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Budget, CancelToken, Limit};
+    use crate::core::{SearchConfig, Shape, node::Node};
+
+    fn start() -> Node {
+        Node::new(Node::default_board(Shape::square(3))).unwrap()
+    }
+
+    #[test]
+    fn test_unlimited_budget() {
+        let mut budget = Budget::new(&SearchConfig::default(), &start());
+
+        assert!((0..10_000).all(|i| budget.expand(i)));
+        assert!(budget.within_depth(1000));
+        assert_eq!(budget.stopped_by(), None);
+    }
+
+    #[test]
+    fn test_node_limit() {
+        let config = SearchConfig::builder().max_nodes(3).build().unwrap();
+        let mut budget = Budget::new(&config, &start());
+
+        assert!(budget.expand(0) && budget.expand(0) && budget.expand(0));
+        assert!(!budget.expand(0));
+        assert_eq!(budget.stopped_by(), Some(Limit::Nodes));
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let mut budget = Budget::new(
+            &SearchConfig::builder().max_depth(5).build().unwrap(),
+            &start(),
+        );
+
+        assert!(budget.within_depth(4));
+        assert!(!budget.within_depth(5));
+        assert!(budget.expand(0));
+        assert_eq!(budget.stopped_by(), Some(Limit::Depth));
+    }
+
+    #[test]
+    fn test_memory_and_time_limits() {
+        let config = SearchConfig::builder().max_memory(1024).build().unwrap();
+        let mut budget = Budget::new(&config, &start());
+        assert!(!budget.expand(1024));
+        assert_eq!(budget.stopped_by(), Some(Limit::Memory));
+
+        let config = SearchConfig::builder()
+            .timeout(Duration::ZERO)
+            .build()
+            .unwrap();
+        let mut budget = Budget::new(&config, &start());
+        assert!(!budget.expand(0));
+        assert_eq!(budget.stopped_by(), Some(Limit::Time));
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let token = CancelToken::new();
+        let config = SearchConfig::builder()
+            .cancel_token(token.clone())
+            .build()
+            .unwrap();
+        let mut budget = Budget::new(&config, &start());
+
+        assert!((0..1000).all(|i| budget.expand(i)));
+        token.cancel();
+        assert!((0..1000).any(|i| !budget.expand(i)));
+        assert_eq!(budget.stopped_by(), Some(Limit::Cancelled));
+    }
+}
@@ -1,14 +1,37 @@
 pub mod algorithm;
+pub mod board;
+pub mod config;
 pub mod direction;
+pub mod error;
+pub mod generator;
+pub mod goal;
+pub mod input;
+pub mod limits;
+pub mod moves;
 pub mod node;
-pub mod order;
+pub mod packed;
 pub mod problem;
+pub mod shape;
 pub mod solution;
 pub mod solver;
+pub mod tree;
+pub mod verify;
 
 pub(crate) use algorithm::Algorithm;
 pub(crate) use algorithm::AlgorithmCommon;
+pub use algorithm::{Iteration, SideStats, Sides};
+pub use board::Board;
+pub use config::{ConfigError, SearchConfig, SearchConfigBuilder};
 pub use direction::Dir;
-pub use order::Order;
+pub use error::SolveError;
+pub use generator::{GenerateError, Generator};
+pub use goal::Goal;
+pub use input::{ParseError, ParseErrorKind};
+pub use limits::{CancelToken, Limit, SearchLimits};
+pub use moves::{MoveError, Moves};
+pub use node::Tile;
+pub use packed::PackedBoard;
 pub use problem::Problem;
+pub use shape::Shape;
 pub use solution::Solution;
+pub use verify::{VerifyError, verify};
@@ -0,0 +1,181 @@
+use std::{error, fmt, ops::Add, ops::Deref, str::FromStr};
+
+use super::Dir;
+
+/// Sequence of moves of the blank, written as a string like `RDLU`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Moves(Vec<Dir>);
+
+impl Moves {
+    pub fn new(dirs: Vec<Dir>) -> Self {
+        Self(dirs)
+    }
+
+    /// Moves that undo these, each reversed, last move first.
+    pub fn inverse(&self) -> Self {
+        self.0.iter().rev().map(|dir| dir.reverse()).collect()
+    }
+
+    /// The same moves with every move directly undone by the next one
+    /// removed, repeatedly, so `RUDL` becomes empty.
+    ///
+    /// # Behavior
+    /// Keeps the result on a stack, a move either cancels the last kept
+    /// move or is kept itself. The board reached is the same, as long as
+    /// every original move was possible.
+    pub fn cancel(&self) -> Self {
+        let mut kept: Vec<Dir> = Vec::with_capacity(self.0.len());
+        for &dir in &self.0 {
+            if kept.last() == Some(&dir.reverse()) {
+                kept.pop();
+            } else {
+                kept.push(dir);
+            }
+        }
+
+        Self(kept)
+    }
+
+    /// These moves followed by `other`.
+    pub fn concat(&self, other: &Moves) -> Self {
+        self.0.iter().chain(&other.0).copied().collect()
+    }
+
+    pub fn into_vec(self) -> Vec<Dir> {
+        self.0
+    }
+}
+
+impl Deref for Moves {
+    type Target = [Dir];
+
+    fn deref(&self) -> &[Dir] {
+        &self.0
+    }
+}
+
+impl From<Vec<Dir>> for Moves {
+    fn from(dirs: Vec<Dir>) -> Self {
+        Self(dirs)
+    }
+}
+
+impl FromIterator<Dir> for Moves {
+    fn from_iter<I: IntoIterator<Item = Dir>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Add<Moves> for Moves {
+    type Output = Self;
+    fn add(mut self, other: Self) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+impl fmt::Display for Moves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for dir in &self.0 {
+            write!(f, "{}", dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Moves {
+    type Err = MoveError;
+
+    /// Reads moves from the letters `U`, `D`, `L` and `R`, skipping whitespace.
+    fn from_str(s: &str) -> Result<Self, MoveError> {
+        s.chars()
+            .enumerate()
+            .filter(|(_, ch)| !ch.is_whitespace())
+            .map(|(position, ch)| Dir::from_char(ch).ok_or(MoveError::InvalidChar { position, ch }))
+            .collect()
+    }
+}
+
+/// Reason why moves could not be read or made.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveError {
+    /// A character of the move string is not one of `U`, `D`, `L` or `R`,
+    /// `position` counting characters from 0.
+    InvalidChar { position: usize, ch: char },
+    /// Move number `index`, counting from 0, would take the blank off the board.
+    IllegalMove { index: usize, dir: Dir },
+    /// The moves were given a board that is not a valid puzzle state.
+    InvalidBoard(String),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::InvalidChar { position, ch } => write!(
+                f,
+                "Invalid move '{}' at character {}, expected U, D, L or R",
+                ch,
+                position + 1
+            ),
+            MoveError::IllegalMove { index, dir } => write!(
+                f,
+                "Move {} ({}) is illegal, the blank is at the edge of the board",
+                index + 1,
+                dir
+            ),
+            MoveError::InvalidBoard(reason) => write!(f, "Invalid board: {}", reason),
+        }
+    }
+}
+
+impl error::Error for MoveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{MoveError, Moves};
+    use crate::core::Dir;
+
+    // Test if moves are read skipping whitespace, written back, and bad characters located
+    #[test]
+    fn test_parse_and_format() {
+        let moves: Moves = "RD LU\n".parse().unwrap();
+
+        assert_eq!(*moves, [Dir::Right, Dir::Down, Dir::Left, Dir::Up]);
+        assert_eq!(moves.to_string(), "RDLU");
+        assert_eq!(
+            "RDxU".parse::<Moves>(),
+            Err(MoveError::InvalidChar {
+                position: 2,
+                ch: 'x'
+            })
+        );
+        assert!("".parse::<Moves>().unwrap().is_empty());
+    }
+
+    // Test if inverted moves undo the originals and opposite neighbours cancel out
+    #[test]
+    fn test_inverse_and_cancel() {
+        let moves: Moves = "RDLLU".parse().unwrap();
+
+        assert_eq!(moves.inverse().to_string(), "DRRUL");
+        assert!(moves.concat(&moves.inverse()).cancel().is_empty());
+        assert_eq!(
+            "RUDLLDUR".parse::<Moves>().unwrap().cancel(),
+            Moves::default()
+        );
+        assert_eq!(
+            "RUDDL".parse::<Moves>().unwrap().cancel().to_string(),
+            "RDL"
+        );
+    }
+
+    // Test if concatenation and addition join moves in order
+    #[test]
+    fn test_concat() {
+        let (a, b): (Moves, Moves) = ("RD".parse().unwrap(), "LU".parse().unwrap());
+
+        assert_eq!(a.concat(&b).to_string(), "RDLU");
+        assert_eq!(a + b, "RDLU".parse().unwrap());
+    }
+}
@@ -1,39 +1,51 @@
 use core::fmt;
-use std::{
-    hash::{DefaultHasher, Hash, Hasher},
-    rc::Rc,
+use std::hash::{Hash, Hasher};
+
+use crate::core::{
+    Dir, Goal, MoveError, Moves, Shape,
+    packed::PackedBoard,
+    tree::{NodeId, SearchTree},
 };
 
-use crate::core::Dir;
+/// Number on a tile, 0 being the blank.
+///
+/// Wide enough for every board a `Shape` describes, up to 255x255.
+pub type Tile = u16;
 
-// Represents a puzzle state
-#[derive(Debug, Clone, Hash, Eq, PartialEq, Default)]
+// Represents a puzzle state, its tiles are kept only in the packed key so a
+// child up to 5x5 is made without allocating. The way it was reached is kept
+// in a `SearchTree`
+#[derive(Debug, Clone)]
 pub struct Node {
-    board: Vec<u8>,
-    dim: u8,
-    parent: Option<Rc<Node>>,
-    depth: u8,
+    shape: Shape,
+    // Index of the empty tile
+    blank: u16,
+    depth: u16,
     next_move: Option<Dir>,
-    hash_code: u64,
+    key: PackedBoard,
+    id: NodeId,
 }
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.depth().cmp(&self.depth)
+// Nodes are the same state if their boards are, however they were reached
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
     }
 }
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
     }
 }
 
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in 0..self.dim {
-            for col in 0..self.dim {
-                write!(f, "{:2} ", self.board[(row * self.dim + col) as usize])?;
+        for row in self.get_board().chunks(self.shape.width as usize) {
+            for tile in row {
+                write!(f, "{:2} ", tile)?;
             }
             writeln!(f)?;
         }
@@ -47,104 +59,113 @@ impl Node {
     /// # Arguments
     /// * `vec` - 2D vector representing the puzzle board state
     ///
-    /// # Panics
-    /// Panics if the board is invalid.
-    pub fn new(vec: Vec<Vec<u8>>) -> Self {
-        let board = vec.into_iter().flatten().collect::<Vec<u8>>();
-
-        if !Self::is_board_valid(&board) {
-            panic!("Board is Invalid")
+    /// # Returns
+    /// * `Ok(Node)` for a board of rows of equal length holding every tile
+    ///   from 0 to N exactly once
+    /// * `Err` describing what is wrong with the board otherwise
+    pub fn new(vec: Vec<Vec<Tile>>) -> Result<Self, String> {
+        let cols = vec.first().map_or(0, |row| row.len());
+        if cols == 0 {
+            return Err("Board is empty".to_string());
         }
 
-        Self {
-            hash_code: Node::make_hash(&board),
-            dim: board.len().isqrt() as u8,
-            board,
-            ..Self::default()
+        if let Some(row) = vec.iter().position(|row| row.len() != cols) {
+            return Err(format!(
+                "Rows are of different lengths, row {} has {} tiles instead of {}",
+                row + 1,
+                vec[row].len(),
+                cols
+            ));
         }
-    }
 
-    fn with_parent(board: Vec<u8>, parent: &Node, dir: Dir) -> Result<Self, String> {
+        let (Ok(height), Ok(width)) = (u8::try_from(vec.len()), u8::try_from(cols)) else {
+            return Err(format!(
+                "Board of {}x{} is too large, at most {} rows and columns are supported",
+                vec.len(),
+                cols,
+                u8::MAX
+            ));
+        };
+
+        let board = vec.into_iter().flatten().collect::<Vec<Tile>>();
+
         if !Self::is_board_valid(&board) {
-            return Err("Invalid board".to_string());
+            return Err(format!(
+                "Board must contain every tile from 0 to {} exactly once",
+                board.len() - 1
+            ));
         }
+
         Ok(Self {
-            hash_code: Node::make_hash(&board),
-            board,
-            dim: parent.dim,
-            depth: parent.depth + 1,
-            parent: Some(Rc::new(parent.clone())),
-            next_move: Some(dir),
+            key: PackedBoard::pack(&board),
+            blank: board.iter().position(|&x| x == 0).unwrap() as u16,
+            shape: Shape::new(height, width),
+            depth: 0,
+            next_move: None,
+            id: 0,
         })
     }
 
-    /// Generates a default (solved) board configuration for given size.
+    /// Generates a default (solved) board configuration for given shape.
     ///
     /// # Arguments
-    /// * `size` - The dimension of the board (e.g., 3 for 3x3 puzzle)
+    /// * `shape` - The rows and columns of the board (e.g., `Shape::new(3, 5)`)
     ///
     /// # Returns
     /// * 2D vector representing the solved state
-    pub fn default_board(size: u8) -> Vec<Vec<u8>> {
-        let dim = size;
-        (1..=dim * dim - 1)
+    pub fn default_board(shape: Shape) -> Vec<Vec<Tile>> {
+        (1..shape.cells())
             .chain(std::iter::once(0))
-            .collect::<Vec<u8>>()
-            .chunks(dim as usize)
+            .map(|tile| tile as Tile)
+            .collect::<Vec<Tile>>()
+            .chunks(shape.width as usize)
             .map(|chunk| chunk.to_vec())
             .collect()
     }
 
-    fn is_board_valid(board: &[u8]) -> bool {
-        let mut temp: Vec<u8> = board.to_vec();
+    fn is_board_valid(board: &[Tile]) -> bool {
+        let mut temp: Vec<Tile> = board.to_vec();
         if temp.is_empty() {
             return false;
         }
 
         temp.sort_unstable();
 
-        temp.iter().enumerate().all(|(i, x)| *x == i as u8)
+        temp.iter().enumerate().all(|(i, x)| *x as usize == i)
     }
 
-    /// Checks if the current board state represents a solved puzzle.
+    /// Checks if the current board state is the goal of the puzzle.
     ///
     /// # Returns
-    /// * `true` if the board is in solved state, `false` otherwise
-    pub fn is_solved(&self) -> bool {
-        let mut expected: Vec<u8> = (1..=(self.dim * self.dim)).collect();
-        expected.pop();
-        expected.push(0);
-
-        self.board == expected
-    }
-
-    fn find_zero(&self) -> u8 {
-        self.board.iter().position(|&x| x == 0).unwrap() as u8
+    /// * `true` if the board equals `goal`, `false` otherwise
+    pub fn is_solved(&self, goal: &Goal) -> bool {
+        self.key == *goal.key()
     }
 
-    fn make_hash(board: &[u8]) -> u64 {
-        let mut hasher = DefaultHasher::new();
-
-        board.hash(&mut hasher);
-
-        hasher.finish()
+    /// Index of the empty tile in the board.
+    pub fn blank(&self) -> u16 {
+        self.blank
     }
 
     fn can_move(&self, direction: Dir) -> bool {
-        let empty_field = self.find_zero();
+        let blank = self.blank as usize;
+        let (width, height) = (self.shape.width as usize, self.shape.height as usize);
+
         match direction {
-            Dir::Left => empty_field % self.dim > 0,
-            Dir::Right => empty_field % self.dim < (self.dim - 1),
-            Dir::Down => empty_field / self.dim < (self.dim - 1),
-            Dir::Up => empty_field / self.dim > 0,
+            Dir::Left => self.shape.col(blank) > 0,
+            Dir::Right => self.shape.col(blank) < width - 1,
+            Dir::Down => self.shape.row(blank) < height - 1,
+            Dir::Up => self.shape.row(blank) > 0,
         }
     }
-    pub fn get_board(&self) -> &Vec<u8> {
-        &self.board
+
+    /// Tiles of the board row by row, unpacked from its key.
+    pub fn get_board(&self) -> Vec<Tile> {
+        self.key.unpack(self.shape.cells())
     }
 
-    pub fn get_dim(&self) -> u8 {
-        (self.board.len() as f64).sqrt() as u8
+    pub fn shape(&self) -> Shape {
+        self.shape
     }
 
     /// Gets all valid moves from current state in specified priority order.
@@ -174,41 +195,101 @@ impl Node {
         }
 
         let new_pos = match dir {
-            Dir::Left => self.find_zero() - 1,
-            Dir::Right => self.find_zero() + 1,
-            Dir::Up => self.find_zero() - self.dim,
-            Dir::Down => self.find_zero() + self.dim,
+            Dir::Left => self.blank - 1,
+            Dir::Right => self.blank + 1,
+            Dir::Up => self.blank - self.shape.width as u16,
+            Dir::Down => self.blank + self.shape.width as u16,
         };
 
-        let mut new_board = self.board.clone();
-        let zero_index = self.find_zero() as usize;
+        let zero_index = self.blank as usize;
         let new_index = new_pos as usize;
 
-        new_board.swap(zero_index, new_index);
+        Some(Self {
+            shape: self.shape,
+            blank: new_pos,
+            depth: self.depth + 1,
+            next_move: Some(dir),
+            key: self.key.swap(zero_index, new_index),
+            // Not stored yet, see `SearchTree::add`
+            id: self.id,
+        })
+    }
 
-        Some(Node::with_parent(new_board, self, dir).unwrap())
+    /// Node stored under `id` in `tree`, rebuilt from its packed `key` and
+    /// the index of its `blank`, so open lists need to keep only those and the
+    /// tree index.
+    pub(crate) fn restore(
+        key: PackedBoard,
+        blank: u16,
+        shape: Shape,
+        id: NodeId,
+        tree: &SearchTree,
+    ) -> Self {
+        Self {
+            shape,
+            blank,
+            depth: tree.depth(id),
+            next_move: tree.last_move(id),
+            key,
+            id,
+        }
     }
 
-    pub fn hash_code(&self) -> u64 {
-        self.hash_code
+    /// Node reached by making `moves` one after another.
+    ///
+    /// # Returns
+    /// * `None` if one of the moves is not possible
+    pub fn follow(&self, moves: &[Dir]) -> Option<Self> {
+        moves
+            .iter()
+            .try_fold(self.clone(), |node, &dir| node.get_node_for_move(dir))
+    }
+
+    /// Node reached by making the moves written in `moves`, like `RDLU`.
+    ///
+    /// # Returns
+    /// * `Ok(Node)` after the last move
+    /// * `Err` naming the first character that is not a move, or the first
+    ///   move that would take the blank off the board
+    pub fn apply(&self, moves: &str) -> Result<Self, MoveError> {
+        let moves: Moves = moves.parse()?;
+        self.try_follow(&moves)
+    }
+
+    /// Node reached by making `moves` one after another, like `follow`.
+    ///
+    /// # Returns
+    /// * `Err` naming the first move that would take the blank off the board
+    pub fn try_follow(&self, moves: &[Dir]) -> Result<Self, MoveError> {
+        moves
+            .iter()
+            .enumerate()
+            .try_fold(self.clone(), |node, (index, &dir)| {
+                node.get_node_for_move(dir)
+                    .ok_or(MoveError::IllegalMove { index, dir })
+            })
+    }
+
+    /// Exact key of the board, equal for two nodes only if their boards are.
+    pub fn key(&self) -> &PackedBoard {
+        &self.key
     }
 
     pub fn next_move(&self) -> Option<Dir> {
         self.next_move
     }
 
-    pub fn parent(&self) -> &Option<Rc<Self>> {
-        &self.parent
+    pub fn depth(&self) -> u16 {
+        self.depth
     }
 
-    pub fn depth(&self) -> u8 {
-        self.depth
+    /// Index of the node in the `SearchTree` of its search.
+    pub fn id(&self) -> NodeId {
+        self.id
     }
 
-    pub fn dir_iter(&self) -> NodePathIterator {
-        NodePathIterator {
-            current: Some(Rc::new(self.clone())),
-        }
+    pub(crate) fn set_id(&mut self, id: NodeId) {
+        self.id = id;
     }
 
     /// Gets all child nodes (possible next states) in specified move order.
@@ -241,58 +322,111 @@ impl Node {
     }
 }
 
-pub struct NodePathIterator {
-    pub current: Option<Rc<Node>>,
-}
-
-impl Iterator for NodePathIterator {
-    type Item = Dir;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let current_state = self.current.take();
-
-        if let Some(state) = current_state {
-            let dir = state.next_move();
-
-            self.current = state.parent().as_ref().map(|p| p.clone());
-
-            return dir;
-        }
-
-        None
-    }
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::core::{Dir, node::Node};
+    use crate::core::{
+        Dir, Goal, MoveError, PackedBoard, Shape, Tile, node::Node, tree::SearchTree,
+    };
+
+    #[test]
+    fn test_node_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Node>();
+    }
 
     #[test]
     fn test_new_valid_board() {
         let board = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 0]];
-        let state = Node::new(board.clone());
+        let state = Node::new(board.clone()).unwrap();
         assert_eq!(
-            *state.get_board(),
-            board.into_iter().flatten().collect::<Vec<u8>>()
+            state.get_board(),
+            board.into_iter().flatten().collect::<Vec<Tile>>()
         );
     }
 
     #[test]
-    #[should_panic]
     fn test_new_invalid_board() {
         let board = vec![
             vec![1, 2, 3],
             vec![4, 5, 6],
             vec![7, 8, 9], // Invalid: 9 is out of range
         ];
-        Node::new(board);
+        assert!(Node::new(board).is_err());
+    }
+
+    #[test]
+    fn test_new_rectangular_board() {
+        let wide = Node::new(vec![vec![1, 2, 3], vec![4, 5, 0]]).unwrap();
+        let tall = Node::new(vec![vec![1, 2], vec![3, 4], vec![5, 0]]).unwrap();
+
+        assert_eq!(wide.shape(), Shape::new(2, 3));
+        assert_eq!(tall.shape(), Shape::new(3, 2));
+        assert!(wide.is_solved(&Goal::standard(wide.shape())));
+        assert!(tall.is_solved(&Goal::standard(tall.shape())));
+    }
+
+    #[test]
+    fn test_new_ragged_board() {
+        assert!(Node::new(vec![vec![1, 2, 3], vec![4, 0]]).is_err());
+        assert!(Node::new(vec![]).is_err());
+        assert!(Node::new(vec![vec![]]).is_err());
+    }
+
+    #[test]
+    fn test_default_board() {
+        assert_eq!(
+            Node::default_board(Shape::new(2, 4)),
+            vec![vec![1, 2, 3, 4], vec![5, 6, 7, 0]]
+        );
+        assert_eq!(
+            Node::default_board(Shape::new(3, 2)),
+            vec![vec![1, 2], vec![3, 4], vec![5, 0]]
+        );
     }
 
+    // Test if boards with more tiles than fit in a byte are built and validated
     #[test]
-    fn test_find_zero() {
+    fn test_large_board() {
+        let shape = Shape::square(20);
+        let node = Node::new(Node::default_board(shape)).unwrap();
+
+        assert_eq!(node.get_board().len(), 400);
+        assert_eq!(node.get_board()[398], 399);
+        assert_eq!(node.blank(), 399);
+        assert!(node.get_node_for_move(Dir::Up).is_some());
+
+        let mut duplicate = Node::default_board(shape);
+        duplicate[10][0] = 256;
+        assert!(Node::new(duplicate).is_err());
+    }
+
+    // Test if moves of the blank respect the edges of a board wider than tall
+    #[test]
+    fn test_moves_on_rectangular_board() {
+        let state = Node::new(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 0]]).unwrap();
+        assert_eq!(
+            state.get_valid_moves(Dir::values()),
+            vec![Dir::Up, Dir::Left]
+        );
+
+        let up = state.get_node_for_move(Dir::Up).unwrap();
+        assert_eq!(up.get_board(), vec![1, 2, 3, 0, 5, 6, 7, 4]);
+        assert!(up.get_node_for_move(Dir::Up).is_none());
+        assert!(up.get_node_for_move(Dir::Right).is_none());
+
+        let left = up.get_node_for_move(Dir::Left).unwrap();
+        assert_eq!(left.blank(), 2);
+    }
+
+    #[test]
+    fn test_blank() {
         let board = vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]];
-        let state = Node::new(board);
-        assert_eq!(state.find_zero(), 4);
+        let state = Node::new(board).unwrap();
+        assert_eq!(state.blank(), 4);
+
+        let child = state.get_node_for_move(Dir::Right).unwrap();
+        assert_eq!(child.blank(), 5);
+        assert_eq!(child.get_board()[5], 0);
     }
 
     #[test]
@@ -302,7 +436,7 @@ mod tests {
             vec![4, 5, 6],
             vec![7, 8, 1], // Zero at (2,2)
         ];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
         let moves = state.get_valid_moves(Dir::values());
 
         assert!(moves.contains(&Dir::Down)); // Can move left
@@ -317,7 +451,7 @@ mod tests {
             vec![4, 5, 6],
             vec![7, 8, 1], // Zero at (2,2)
         ];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
 
         // Move left
         if let Some(new_state) = state.get_node_for_move(Dir::Right) {
@@ -327,8 +461,8 @@ mod tests {
                 vec![7, 8, 1], // Zero moved left
             ];
             assert_eq!(
-                *new_state.get_board(),
-                expected_board.into_iter().flatten().collect::<Vec<u8>>()
+                new_state.get_board(),
+                expected_board.into_iter().flatten().collect::<Vec<Tile>>()
             );
         } else {
             panic!("Expected a valid state after move");
@@ -342,7 +476,7 @@ mod tests {
             vec![4, 0, 5], // Zero at (1,1)
             vec![6, 7, 8],
         ];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
         let child_states = state.get_child_nodes(Dir::values());
 
         assert_eq!(child_states.len(), 4); // Can move up, down, left, right
@@ -360,10 +494,12 @@ mod tests {
             vec![4, 5, 6],
             vec![7, 0, 8], // Wrong position
         ];
-        let solved_state = Node::new(solved_board);
-        let unsolved_state = Node::new(unsolved_board);
-        assert!(solved_state.is_solved());
-        assert!(!unsolved_state.is_solved());
+        let solved_state = Node::new(solved_board).unwrap();
+        let unsolved_state = Node::new(unsolved_board).unwrap();
+        let goal = Goal::standard(solved_state.shape());
+        assert!(solved_state.is_solved(&goal));
+        assert!(!unsolved_state.is_solved(&goal));
+        assert!(!solved_state.is_solved(&Goal::blank_first(solved_state.shape())));
     }
     #[test]
     fn test_valid_moves_initial_state() {
@@ -373,7 +509,8 @@ mod tests {
             vec![3, 4, 5, 12],
             vec![6, 7, 8, 13],
             vec![10, 9, 15, 14],
-        ]);
+        ])
+        .unwrap();
 
         // Expected valid moves: Down, Right
         let expected = vec![Dir::Down, Dir::Right];
@@ -384,7 +521,7 @@ mod tests {
     fn test_get_valid_moves_from_top_left() {
         // Arrange
         let board = vec![vec![0, 2, 3], vec![4, 5, 6], vec![7, 8, 1]];
-        let state = Node::new(board); // Act
+        let state = Node::new(board).unwrap(); // Act
         let moves = state.get_valid_moves(Dir::values());
 
         // Assert
@@ -397,7 +534,7 @@ mod tests {
     fn test_get_valid_moves_from_bottom_right() {
         // Arrange
         let board = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 0]];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
 
         // Act
         let moves = state.get_valid_moves(Dir::values());
@@ -412,7 +549,7 @@ mod tests {
     fn test_get_valid_moves_from_center() {
         // Arrange
         let board = vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
         // Act
         let moves = state.get_valid_moves(Dir::values());
 
@@ -428,7 +565,7 @@ mod tests {
     fn test_get_state_for_move_right() {
         // Arrange
         let board = vec![vec![0, 2, 3], vec![4, 5, 6], vec![7, 8, 1]];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
 
         // Act
         let new_state = state.get_node_for_move(Dir::Right).unwrap();
@@ -437,16 +574,16 @@ mod tests {
         let expected_board = vec![vec![2, 0, 3], vec![4, 5, 6], vec![7, 8, 1]]
             .into_iter()
             .flatten()
-            .collect::<Vec<u8>>();
+            .collect::<Vec<Tile>>();
         assert_eq!(
-            *new_state.get_board(),
+            new_state.get_board(),
             expected_board,
             "Zero should have moved right"
         );
         assert_eq!(
-            new_state.parent.as_ref().unwrap().board,
-            state.board,
-            "Parent should be the original state"
+            new_state.depth,
+            state.depth + 1,
+            "Depth should be one more than the original state"
         );
         assert_eq!(
             new_state.next_move,
@@ -459,7 +596,7 @@ mod tests {
     fn test_get_state_for_move_invalid() {
         // Arrange
         let board = vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]];
-        let state = Node::new(board);
+        let state = Node::new(board).unwrap();
 
         // Act
         let new_state = state.get_node_for_move(Dir::Left);
@@ -472,41 +609,38 @@ mod tests {
     }
 
     #[test]
-    fn test_make_hash() {
+    fn test_key() {
         // Arrange
         let board1 = vec![1, 2, 3, 4, 5, 6, 7, 8, 0];
         let board2 = vec![1, 2, 3, 4, 5, 6, 7, 0, 8];
 
         // Act
-        let hash1 = Node::make_hash(&board1);
-        let hash2 = Node::make_hash(&board2);
+        let key1 = PackedBoard::pack(&board1);
+        let key2 = PackedBoard::pack(&board2);
 
         // Assert
-        assert_ne!(
-            hash1, hash2,
-            "Different boards should produce different hashes"
-        );
+        assert_ne!(key1, key2, "Different boards should produce different keys");
 
-        // Test that the same board produces the same hash
+        // Test that the same board produces the same key
         let board3 = vec![1, 2, 3, 4, 5, 6, 7, 8, 0];
-        let hash3 = Node::make_hash(&board3);
-        assert_eq!(hash1, hash3, "Same boards should produce the same hash");
+        let key3 = PackedBoard::pack(&board3);
+        assert_eq!(key1, key3, "Same boards should produce the same key");
     }
 
     #[test]
-    fn test_with_parent() {
+    fn test_child_of_parent() {
         // Arrange
-        let initial_state = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]);
+        let initial_state = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]).unwrap();
 
         // Act
-        let new_board = vec![1, 2, 3, 0, 4, 5, 6, 7, 8];
-        let moved_state = Node::with_parent(new_board, &initial_state, Dir::Left).unwrap();
+        let moved_state = initial_state.get_node_for_move(Dir::Left).unwrap();
 
         // Assert
+        assert_eq!(moved_state.get_board(), vec![1, 2, 3, 0, 4, 5, 6, 7, 8]);
         assert_eq!(
-            moved_state.parent.unwrap().board,
-            initial_state.board,
-            "Parent board should match"
+            *moved_state.key(),
+            PackedBoard::pack(&moved_state.get_board()),
+            "Key should match the board"
         );
         assert_eq!(moved_state.depth, 1, "Depth should be incremented");
         assert_eq!(
@@ -515,4 +649,49 @@ mod tests {
             "Next move should be set"
         );
     }
+
+    // Test if a node rebuilt from its key and tree index equals the stored one
+    #[test]
+    fn test_restore() {
+        let mut root = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]).unwrap();
+        let mut tree = SearchTree::new(&mut root);
+        let mut child = root.get_node_for_move(Dir::Left).unwrap();
+        tree.add(&root, &mut child);
+
+        let restored = Node::restore(
+            child.key().clone(),
+            child.blank(),
+            child.shape(),
+            child.id(),
+            &tree,
+        );
+
+        assert_eq!(restored, child);
+        assert_eq!(restored.blank(), child.blank());
+        assert_eq!(restored.depth(), 1);
+        assert_eq!(restored.next_move(), Some(Dir::Left));
+    }
+
+    #[test]
+    fn test_apply() {
+        let start = Node::new(vec![vec![1, 2, 3], vec![4, 0, 5], vec![6, 7, 8]]).unwrap();
+
+        let moved = start.apply("RDLU").unwrap();
+        assert_eq!(moved.get_board(), vec![1, 2, 3, 4, 0, 8, 6, 5, 7]);
+        assert_eq!(moved.depth, 4);
+        assert_eq!(start.apply("").unwrap().get_board(), start.get_board());
+
+        // The second move right leaves the board
+        assert_eq!(
+            start.apply("RRD").unwrap_err(),
+            MoveError::IllegalMove {
+                index: 1,
+                dir: Dir::Right
+            }
+        );
+        assert!(matches!(
+            start.apply("RX"),
+            Err(MoveError::InvalidChar { position: 1, .. })
+        ));
+    }
 }
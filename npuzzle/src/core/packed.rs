@@ -0,0 +1,151 @@
+use super::Tile;
+
+/// A board packed into as few bits as its size allows, used as an exact key of
+/// a puzzle state.
+///
+/// Unlike a hash, two packed boards are equal only if the boards are, so
+/// visited sets keyed on them never prune a state that was not seen.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PackedBoard {
+    /// Up to 16 tiles of 4 bits each, boards up to 4x4.
+    Nibbles(u64),
+    /// Up to 25 tiles of 5 bits each, boards up to 5x5.
+    Quints(u128),
+    /// One byte per tile, boards of up to 256 tiles such as 16x16.
+    Bytes(Box<[u8]>),
+    /// Whole tiles for larger boards.
+    Wide(Box<[Tile]>),
+}
+
+impl PackedBoard {
+    /// Packs a board given row by row, the first tile in the lowest bits.
+    /// Every tile must be smaller than the number of tiles.
+    pub fn pack(board: &[Tile]) -> Self {
+        match board.len() {
+            0..=16 => PackedBoard::Nibbles(
+                board
+                    .iter()
+                    .rev()
+                    .fold(0, |packed, &tile| packed << 4 | tile as u64),
+            ),
+            17..=25 => PackedBoard::Quints(
+                board
+                    .iter()
+                    .rev()
+                    .fold(0, |packed, &tile| packed << 5 | tile as u128),
+            ),
+            26..=256 => PackedBoard::Bytes(board.iter().map(|&tile| tile as u8).collect()),
+            _ => PackedBoard::Wide(board.into()),
+        }
+    }
+
+    /// Unpacks the `len` tiles of the board.
+    pub fn unpack(&self, len: usize) -> Vec<Tile> {
+        match self {
+            PackedBoard::Nibbles(packed) => (0..len)
+                .map(|i| (packed >> (4 * i) & 0xf) as Tile)
+                .collect(),
+            PackedBoard::Quints(packed) => (0..len)
+                .map(|i| (packed >> (5 * i) & 0x1f) as Tile)
+                .collect(),
+            PackedBoard::Bytes(tiles) => tiles.iter().map(|&tile| tile as Tile).collect(),
+            PackedBoard::Wide(tiles) => tiles.to_vec(),
+        }
+    }
+
+    /// Tile at `index`, read without unpacking the board.
+    pub fn tile(&self, index: usize) -> Tile {
+        match self {
+            PackedBoard::Nibbles(packed) => (packed >> (4 * index) & 0xf) as Tile,
+            PackedBoard::Quints(packed) => (packed >> (5 * index) & 0x1f) as Tile,
+            PackedBoard::Bytes(tiles) => tiles[index] as Tile,
+            PackedBoard::Wide(tiles) => tiles[index],
+        }
+    }
+
+    /// Bytes taken by the packed board, including those on the heap.
+    pub fn memory(&self) -> usize {
+        size_of::<Self>()
+            + match self {
+                PackedBoard::Nibbles(_) | PackedBoard::Quints(_) => 0,
+                PackedBoard::Bytes(tiles) => size_of_val(&**tiles),
+                PackedBoard::Wide(tiles) => size_of_val(&**tiles),
+            }
+    }
+
+    /// Packed board after the tiles at `a` and `b` are swapped.
+    pub fn swap(&self, a: usize, b: usize) -> Self {
+        match self {
+            PackedBoard::Nibbles(packed) => {
+                let (ta, tb) = (packed >> (4 * a) & 0xf, packed >> (4 * b) & 0xf);
+                let diff = ta ^ tb;
+                PackedBoard::Nibbles(packed ^ (diff << (4 * a)) ^ (diff << (4 * b)))
+            }
+            PackedBoard::Quints(packed) => {
+                let (ta, tb) = (packed >> (5 * a) & 0x1f, packed >> (5 * b) & 0x1f);
+                let diff = ta ^ tb;
+                PackedBoard::Quints(packed ^ (diff << (5 * a)) ^ (diff << (5 * b)))
+            }
+            PackedBoard::Bytes(tiles) => {
+                let mut tiles = tiles.clone();
+                tiles.swap(a, b);
+                PackedBoard::Bytes(tiles)
+            }
+            PackedBoard::Wide(tiles) => {
+                let mut tiles = tiles.clone();
+                tiles.swap(a, b);
+                PackedBoard::Wide(tiles)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PackedBoard;
+    use crate::core::Tile;
+
+    // Test if every supported size packs into the smallest representation and back
+    #[test]
+    fn test_pack_round_trip() {
+        for len in [4, 9, 16, 25, 36, 256, 400] {
+            let board: Vec<Tile> = (0..len as Tile).rev().collect();
+            let packed = PackedBoard::pack(&board);
+
+            let expected_variant = match len {
+                0..=16 => matches!(packed, PackedBoard::Nibbles(_)),
+                17..=25 => matches!(packed, PackedBoard::Quints(_)),
+                26..=256 => matches!(packed, PackedBoard::Bytes(_)),
+                _ => matches!(packed, PackedBoard::Wide(_)),
+            };
+            assert!(
+                expected_variant,
+                "Unexpected representation for {} tiles",
+                len
+            );
+            assert_eq!(packed.unpack(len), board);
+            assert!((0..len).all(|i| packed.tile(i) == board[i]));
+        }
+    }
+
+    // Test if swapping packed tiles matches swapping the board
+    #[test]
+    fn test_swap() {
+        for len in [9, 16, 25, 36, 400] {
+            let mut board: Vec<Tile> = (0..len as Tile).collect();
+            let packed = PackedBoard::pack(&board).swap(0, len - 1);
+            board.swap(0, len - 1);
+
+            assert_eq!(packed, PackedBoard::pack(&board));
+        }
+    }
+
+    // Test if boards differing in a single pair of tiles get different keys
+    #[test]
+    fn test_distinct_boards_distinct_keys() {
+        let a = PackedBoard::pack(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+        let b = PackedBoard::pack(&[2, 1, 3, 4, 5, 6, 7, 8, 0]);
+
+        assert_ne!(a, b);
+    }
+}
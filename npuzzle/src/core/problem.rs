@@ -1,63 +1,308 @@
+use super::{Goal, MoveError, ParseError, Shape, Tile, input::parse_board, node::Node};
+
 #[derive(Clone, Debug)]
 pub struct Problem {
-    table: Vec<Vec<u8>>,
+    table: Vec<Vec<Tile>>,
+    goal: Option<Goal>,
 }
 
 impl Problem {
-    pub fn new(table: Vec<Vec<u8>>) -> Problem {
-        Problem { table }
+    /// Puzzle solved towards the standard goal, see `Goal::standard`.
+    pub fn new(table: Vec<Vec<Tile>>) -> Problem {
+        Problem { table, goal: None }
+    }
+
+    /// Puzzle solved towards `goal`.
+    pub fn with_goal(table: Vec<Vec<Tile>>, goal: Goal) -> Problem {
+        Problem {
+            table,
+            goal: Some(goal),
+        }
     }
 
-    pub fn get(&self) -> Vec<Vec<u8>> {
+    pub fn get(&self) -> Vec<Vec<Tile>> {
         self.table.clone()
     }
 
-    pub fn from_string(s: &str) -> Result<Self, String> {
-        let mut table = Vec::new();
-        let mut empty_count = 0;
+    /// Number of rows of the table and of tiles in its first row.
+    pub fn shape(&self) -> Shape {
+        let width = self.table.first().map_or(0, |row| row.len());
 
-        for line in s.lines() {
-            let line = line.trim();
-            if line.is_empty() {
-                continue;
-            }
+        Shape::new(
+            u8::try_from(self.table.len()).unwrap_or(u8::MAX),
+            u8::try_from(width).unwrap_or(u8::MAX),
+        )
+    }
 
-            let row: Vec<u8> = line
-                .split_whitespace()
-                .map(|x| {
-                    x.parse::<u8>()
-                        .map_err(|_| format!("Invalid value in table: {}", x))
-                })
-                .collect::<Result<Vec<u8>, String>>()?;
+    /// The state the puzzle is solved towards.
+    pub fn goal(&self) -> Goal {
+        match &self.goal {
+            Some(goal) => goal.clone(),
+            None => Goal::standard(self.shape()),
+        }
+    }
 
-            if row.is_empty() {
-                return Err("Pusta linia w pliku".to_string());
-            }
+    /// Puzzle with the board reached by making the moves written in `moves`,
+    /// like `RDLU`, solved towards the same goal.
+    ///
+    /// # Returns
+    /// * `Ok(Problem)` after the last move
+    /// * `Err` if the board is invalid, a character is not a move, or a move
+    ///   would take the blank off the board
+    pub fn apply(&self, moves: &str) -> Result<Problem, MoveError> {
+        let node = Node::new(self.get())
+            .map_err(MoveError::InvalidBoard)?
+            .apply(moves)?;
+        let table = node
+            .get_board()
+            .chunks(node.shape().width as usize)
+            .map(|row| row.to_vec())
+            .collect();
 
-            empty_count += row.iter().filter(|&&x| x == 0).count();
-            table.push(row);
-        }
+        Ok(Problem {
+            table,
+            goal: self.goal.clone(),
+        })
+    }
 
-        if empty_count != 1 {
-            return Err("Nieprawidłowa liczba pustych pól".to_string());
-        }
+    /// Checks whether the goal can be reached, by the parity of the
+    /// permutation of tiles.
+    ///
+    /// A horizontal move never changes the number of inversions. A vertical
+    /// move passes a tile over `width - 1` others, so on boards of odd width
+    /// the parity of inversions is fixed, and on boards of even width it
+    /// changes together with the row of the blank. The board and the goal
+    /// must agree on that parity.
+    ///
+    /// On a board of a single row or column the tiles can never pass each
+    /// other, so they must already be in the order of the goal.
+    ///
+    /// # Returns
+    /// * `true` if the puzzle has a solution, `false` otherwise
+    pub fn is_solvable(&self) -> bool {
+        let tiles: Vec<Tile> = self.table.iter().flatten().copied().collect();
+        let goal = self.goal();
+        let shape = goal.shape();
 
-        if table.is_empty() {
-            return Err("Pusty plik".to_string());
+        if self.shape() != shape {
+            return false;
         }
-
-        let rows = table.len();
-        let cols = table[0].len();
-        if rows == 0 || cols == 0 {
-            return Err("Nieprawidłowy format pliku".to_string());
+        if shape.height == 1 || shape.width == 1 {
+            let numbered = |tiles: &[Tile]| {
+                tiles
+                    .iter()
+                    .filter(|&&tile| tile != 0)
+                    .copied()
+                    .collect::<Vec<Tile>>()
+            };
+            return numbered(&tiles) == numbered(goal.tiles());
         }
 
-        for row in &table {
-            if row.len() != cols {
-                return Err("Nierówne wiersze w pliku".to_string());
+        parity(&tiles, shape) == parity(goal.tiles(), shape)
+    }
+
+    /// Reads a puzzle in any of the input formats, see `input::parse_board`,
+    /// solved towards the standard goal.
+    pub fn from_string(s: &str) -> Result<Self, ParseError> {
+        Ok(Problem::new(parse_board(s)?))
+    }
+}
+
+// Parity of the inversions of the tiles, together with the row of the blank on
+// boards of even width, that no move changes
+fn parity(tiles: &[Tile], shape: Shape) -> bool {
+    let inversions = tiles
+        .iter()
+        .enumerate()
+        .filter(|&(_, &tile)| tile != 0)
+        .map(|(i, &tile)| {
+            tiles[i + 1..]
+                .iter()
+                .filter(|&&other| other != 0 && other < tile)
+                .count()
+        })
+        .sum::<usize>();
+
+    if !shape.width.is_multiple_of(2) {
+        return !inversions.is_multiple_of(2);
+    }
+
+    let blank_row = shape.row(tiles.iter().position(|&tile| tile == 0).unwrap_or(0));
+    !(inversions + blank_row).is_multiple_of(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use super::Problem;
+    use crate::core::Moves;
+    use crate::core::{Dir, Goal, PackedBoard, Shape, Tile, node::Node};
+
+    // Test if the solved board is solvable
+    #[test]
+    fn test_solved_board_is_solvable() {
+        let odd = Problem::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 0]]);
+        let even = Problem::new(vec![vec![1, 2], vec![3, 0]]);
+
+        assert!(odd.is_solvable());
+        assert!(even.is_solvable());
+    }
+
+    // Test if swapping two tiles makes a board of odd width unsolvable
+    #[test]
+    fn test_odd_width_parity() {
+        let swapped = Problem::new(vec![vec![2, 1, 3], vec![4, 5, 6], vec![7, 8, 0]]);
+        let scrambled = Problem::new(vec![vec![4, 1, 3], vec![7, 2, 6], vec![0, 5, 8]]);
+
+        assert!(!swapped.is_solvable());
+        assert!(scrambled.is_solvable());
+    }
+
+    // Test if the blank row is taken into account on boards of even width
+    #[test]
+    fn test_even_width_parity() {
+        let solvable = Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ]);
+        // Tiles in order, but the blank one row above its goal row
+        let unsolvable = Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 0],
+            vec![12, 13, 14, 15],
+        ]);
+        let swapped = Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12],
+            vec![13, 15, 14, 0],
+        ]);
+
+        assert!(solvable.is_solvable());
+        assert!(!unsolvable.is_solvable());
+        assert!(!swapped.is_solvable());
+    }
+
+    // Test if boards that are not square follow the parity rule of their width
+    #[test]
+    fn test_rectangular_parity() {
+        // Even width: the blank moved up one row from the goal
+        let moved = Problem::new(vec![vec![1, 2, 3, 0], vec![5, 6, 7, 4]]);
+        let swapped = Problem::new(vec![vec![2, 1, 3, 4], vec![5, 6, 7, 0]]);
+        // Odd width
+        let tall = Problem::new(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+            vec![10, 11, 0],
+        ]);
+        let tall_swapped = Problem::new(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+            vec![11, 10, 0],
+        ]);
+
+        assert!(moved.is_solvable());
+        assert!(!swapped.is_solvable());
+        assert!(tall.is_solvable());
+        assert!(!tall_swapped.is_solvable());
+    }
+
+    // Test if exactly the boards reachable from the goal are reported solvable,
+    // by walking every state of small rectangular boards
+    #[test]
+    fn test_solvability_matches_reachability() {
+        let shapes = [
+            Shape::new(2, 3),
+            Shape::new(3, 2),
+            Shape::new(2, 4),
+            Shape::new(1, 4),
+            Shape::new(4, 1),
+        ];
+        let goals = shapes.into_iter().flat_map(|shape| {
+            [
+                Goal::standard(shape),
+                Goal::blank_first(shape),
+                Goal::spiral(shape),
+            ]
+        });
+
+        for goal in goals {
+            let shape = goal.shape();
+            let goal_node = Node::new(goal.table()).unwrap();
+            let mut reachable = HashSet::from([goal_node.key().clone()]);
+            let mut queue = VecDeque::from([goal_node]);
+
+            while let Some(node) = queue.pop_front() {
+                for child in node.get_child_nodes(Dir::values()) {
+                    if reachable.insert(child.key().clone()) {
+                        queue.push_back(child);
+                    }
+                }
+            }
+
+            let mut tiles: Vec<Tile> = (0..shape.cells() as Tile).collect();
+            loop {
+                let table = tiles
+                    .chunks(shape.width as usize)
+                    .map(|row| row.to_vec())
+                    .collect();
+                assert_eq!(
+                    Problem::with_goal(table, goal.clone()).is_solvable(),
+                    reachable.contains(&PackedBoard::pack(&tiles)),
+                    "{:?} towards {:?}",
+                    tiles,
+                    goal.tiles()
+                );
+
+                if !next_permutation(&mut tiles) {
+                    break;
+                }
             }
+            // Tiles of a single line keep their order, only the blank moves
+            let expected = if shape.height == 1 || shape.width == 1 {
+                shape.cells()
+            } else {
+                (1..=shape.cells()).product::<usize>() / 2
+            };
+            assert_eq!(reachable.len(), expected);
         }
+    }
+
+    // Test if applying moves and their inverse returns to the board and keeps the goal
+    #[test]
+    fn test_apply_and_inverse() {
+        let goal = Goal::spiral(Shape::square(3));
+        let problem = Problem::with_goal(goal.table(), goal.clone());
+        let moves: Moves = "LURRDDLU".parse().unwrap();
+
+        let moved = problem.apply(&moves.to_string()).unwrap();
+        assert_ne!(moved.get(), problem.get());
+        assert_eq!(moved.goal(), goal);
+        assert_eq!(
+            moved.apply(&moves.inverse().to_string()).unwrap().get(),
+            problem.get()
+        );
+        assert!(problem.apply("UU").is_err());
+    }
+
+    // Rearranges `tiles` into the next permutation in lexicographic order
+    fn next_permutation(tiles: &mut [Tile]) -> bool {
+        let Some(i) = (1..tiles.len()).rev().find(|&i| tiles[i - 1] < tiles[i]) else {
+            return false;
+        };
+        let j = (i..tiles.len())
+            .rev()
+            .find(|&j| tiles[j] > tiles[i - 1])
+            .unwrap();
 
-        Ok(Problem::new(table))
+        tiles.swap(i - 1, j);
+        tiles[i..].reverse();
+        true
     }
 }
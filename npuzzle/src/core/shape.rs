@@ -0,0 +1,60 @@
+use std::fmt;
+
+/// Number of rows and columns of a board, written as `rows`x`cols`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    pub height: u8,
+    pub width: u8,
+}
+
+impl Shape {
+    pub fn new(height: u8, width: u8) -> Self {
+        Self { height, width }
+    }
+
+    /// Shape of a `dim`x`dim` board.
+    pub fn square(dim: u8) -> Self {
+        Self::new(dim, dim)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.height == self.width
+    }
+
+    /// Number of cells, the blank included.
+    pub fn cells(&self) -> usize {
+        self.height as usize * self.width as usize
+    }
+
+    /// Row of the cell at `index`, counting from the top.
+    pub fn row(&self, index: usize) -> usize {
+        index / self.width as usize
+    }
+
+    /// Column of the cell at `index`, counting from the left.
+    pub fn col(&self, index: usize) -> usize {
+        index % self.width as usize
+    }
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.height, self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Shape;
+
+    #[test]
+    fn test_rows_and_columns() {
+        let shape = Shape::new(2, 4);
+
+        assert_eq!(shape.cells(), 8);
+        assert_eq!((shape.row(5), shape.col(5)), (1, 1));
+        assert_eq!((shape.row(3), shape.col(3)), (0, 3));
+        assert!(!shape.is_square());
+        assert_eq!(shape.to_string(), "2x4");
+    }
+}
@@ -1,21 +1,34 @@
 use std::ops::Add;
 
-use super::Dir;
+use super::{
+    Dir,
+    algorithm::{Iteration, Sides},
+};
 
 #[derive(Debug)]
 pub struct Solution {
     pub visited_count: usize,
     pub processed_count: usize,
-    pub reached_depth: i16,
-    pub result_len: u8,
+    pub reached_depth: i32,
+    pub result_len: u16,
     pub duration: f64,
     pub path: Vec<Dir>,
+    pub iterations: Vec<Iteration>,
+    pub sides: Option<Sides>,
+    pub suboptimality_bound: Option<f64>,
 }
 impl Clone for Solution {
     fn clone(&self) -> Solution {
         Self {
+            visited_count: self.visited_count,
+            processed_count: self.processed_count,
+            reached_depth: self.reached_depth,
+            result_len: self.result_len,
+            duration: self.duration,
             path: vec![],
-            ..*self
+            iterations: self.iterations.clone(),
+            sides: self.sides,
+            suboptimality_bound: self.suboptimality_bound,
         }
     }
 }
@@ -30,6 +43,9 @@ impl Add<Solution> for Solution {
             result_len: self.result_len + other.result_len,
             duration: self.duration + other.duration,
             path: vec![],
+            iterations: vec![],
+            sides: None,
+            suboptimality_bound: None,
         }
     }
 }
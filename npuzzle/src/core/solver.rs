@@ -1,13 +1,19 @@
 use std::time::Instant;
 
-use crate::algorithms::{AStar, Bfs, Dfs};
+use crate::algorithms::{AStar, Bfs, BiBfs, Dfs, IdaStar, Iddfs};
 
-use super::{Algorithm, Dir, Order, Problem, Solution, node::Node};
+use super::{
+    Algorithm, AlgorithmCommon, ConfigError, Limit, Problem, SearchConfig, Solution, SolveError,
+    node::Node,
+};
 
 pub enum SolverEnum {
     Bfs(BfsSolver),
     Dfs(DfsSolver),
     AStar(AStarSolver),
+    IdaStar(IdaStarSolver),
+    Iddfs(IddfsSolver),
+    BiBfs(BiBfsSolver),
 }
 impl Clone for SolverEnum {
     fn clone(&self) -> Self {
@@ -15,6 +21,9 @@ impl Clone for SolverEnum {
             SolverEnum::Bfs(solver) => SolverEnum::Bfs((*solver).clone()),
             SolverEnum::Dfs(solver) => SolverEnum::Dfs((*solver).clone()),
             SolverEnum::AStar(solver) => SolverEnum::AStar((*solver).clone()),
+            SolverEnum::IdaStar(solver) => SolverEnum::IdaStar((*solver).clone()),
+            SolverEnum::Iddfs(solver) => SolverEnum::Iddfs((*solver).clone()),
+            SolverEnum::BiBfs(solver) => SolverEnum::BiBfs((*solver).clone()),
         }
     }
 }
@@ -22,56 +31,116 @@ impl Clone for SolverEnum {
 pub type BfsSolver = Solver<Bfs>;
 pub type DfsSolver = Solver<Dfs>;
 pub type AStarSolver = Solver<AStar>;
+pub type IdaStarSolver = Solver<IdaStar>;
+pub type IddfsSolver = Solver<Iddfs>;
+pub type BiBfsSolver = Solver<BiBfs>;
 
 pub struct Solver<T: Algorithm> {
-    pub order: Order,
+    config: SearchConfig,
     pub algorithm: T,
 }
 
-impl<T: Algorithm + Default> Clone for Solver<T> {
+impl<T: Algorithm + Clone> Clone for Solver<T> {
+    /// Clones the solver configuration, without statistics of previous runs.
     fn clone(&self) -> Self {
+        let mut algorithm = self.algorithm.clone();
+        *algorithm = AlgorithmCommon::new();
+
         Self {
-            order: self.order.clone(),
-            algorithm: T::new(),
+            config: self.config.clone(),
+            algorithm,
         }
     }
 }
 
+impl Solver<AStar> {
+    /// Weighted A* solver, solution cost is at most `weight` times the optimum.
+    pub fn weighted(config: SearchConfig, weight: f64) -> Result<Self, ConfigError> {
+        Self::with_algorithm(config, AStar::weighted(weight))
+    }
+
+    /// Greedy best-first solver, fast but without any bound on solution cost.
+    pub fn greedy(config: SearchConfig) -> Result<Self, ConfigError> {
+        Self::with_algorithm(config, AStar::greedy())
+    }
+}
+
+impl<T: Algorithm> Solver<T> {
+    /// Pairs the algorithm with a configuration, failing if the algorithm
+    /// cannot run with it.
+    pub fn with_algorithm(config: SearchConfig, algorithm: T) -> Result<Self, ConfigError> {
+        algorithm.validate(&config)?;
+        Ok(Self { config, algorithm })
+    }
+
+    pub fn config(&self) -> &SearchConfig {
+        &self.config
+    }
+}
+
 impl<T: Algorithm + Default> Solver<T> {
-    pub fn new(order: Order) -> Self {
-        Self {
-            algorithm: T::new(),
-            order,
-        }
+    pub fn new(config: SearchConfig) -> Result<Self, ConfigError> {
+        Self::with_algorithm(config, T::new())
     }
 
-    pub fn solve(&mut self, problem: Problem) -> Option<Solution> {
-        let start = Instant::now();
-        let result = self.algorithm.run(problem, &self.order);
-        let duration = start.elapsed().as_micros() as f64 / 1000.0;
-
-        if let Some(result) = result {
-            return Some(Solution {
-                visited_count: self.algorithm.get_visited_count(),
-                processed_count: self.algorithm.get_processed_count(),
-                reached_depth: self.algorithm.get_reached_depth(),
-                result_len: self.algorithm.get_result_len(),
-                duration,
-                path: Solver::<T>::make_path(result),
-            });
+    /// Solves the problem with the configured algorithm.
+    ///
+    /// # Returns
+    /// * `Ok(Solution)` with the path and statistics of the search
+    /// * `Err(SolveError)` telling why there is no solution, with the statistics
+    ///   gathered so far if the search gave up or was cancelled through the
+    ///   `CancelToken` of the configuration
+    pub fn solve(&mut self, problem: Problem) -> Result<Solution, SolveError> {
+        let start = Node::new(problem.get()).map_err(SolveError::InvalidBoard)?;
+        let goal = problem.goal();
+        if goal.shape() != start.shape() {
+            return Err(SolveError::InvalidBoard(format!(
+                "Board is {} but the goal is {}",
+                start.shape(),
+                goal.shape()
+            )));
         }
+        self.config.check_goal(&goal)?;
 
-        None
-    }
+        if !problem.is_solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+
+        *self.algorithm = AlgorithmCommon::new();
+        let timer = Instant::now();
+        let result = self.algorithm.run(problem, &self.config);
+        let duration = timer.elapsed().as_micros() as f64 / 1000.0;
 
-    fn make_path(state: Node) -> Vec<Dir> {
-        let mut directions = Vec::new();
+        match result {
+            Some(_) => Ok(self.make_solution(duration)),
+            None => {
+                let stats = Box::new(self.make_solution(duration));
 
-        for x in state.dir_iter() {
-            directions.push(x);
+                Err(match self.algorithm.get_stopped_by() {
+                    Some(Limit::Depth) => SolveError::DepthLimitReached(stats),
+                    Some(Limit::Nodes) => SolveError::NodeLimitReached(stats),
+                    Some(Limit::Time) => SolveError::Timeout(stats),
+                    Some(Limit::Memory) => SolveError::MemoryLimitReached(stats),
+                    Some(Limit::Cancelled) => SolveError::Cancelled(stats),
+                    // Every reachable state was searched, only possible if
+                    // the solvability check missed something
+                    None => SolveError::Unsolvable,
+                })
+            }
         }
+    }
 
-        directions.reverse();
-        directions
+    fn make_solution(&self, duration: f64) -> Solution {
+        Solution {
+            visited_count: self.algorithm.get_visited_count(),
+            processed_count: self.algorithm.get_processed_count(),
+            reached_depth: self.algorithm.get_reached_depth(),
+            result_len: self.algorithm.get_result_len(),
+            duration,
+            path: self.algorithm.get_path().to_vec(),
+            iterations: self.algorithm.get_iterations().to_vec(),
+            sides: self.algorithm.get_sides(),
+            suboptimality_bound: self.algorithm.get_suboptimality_bound(),
+        }
     }
 }
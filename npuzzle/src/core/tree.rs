@@ -0,0 +1,105 @@
+use super::{Dir, node::Node};
+
+/// Index of a node in a `SearchTree`.
+pub type NodeId = u32;
+
+// Link of a stored node to its parent, the root links to itself
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    parent: NodeId,
+    depth: u16,
+    dir: Option<Dir>,
+}
+
+/// Arena holding how every stored node of a search was reached.
+///
+/// Nodes only keep their index into the tree, so the states in open lists and
+/// visited sets do not keep their ancestors alive. The path to a node is
+/// rebuilt by following the parent indices back to the root.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SearchTree {
+    entries: Vec<Entry>,
+}
+
+impl SearchTree {
+    /// Bytes taken by each stored node.
+    pub(crate) const ENTRY_SIZE: usize = size_of::<Entry>();
+
+    /// Starts a tree rooted at `root`.
+    pub(crate) fn new(root: &mut Node) -> Self {
+        let mut tree = Self::default();
+        root.set_id(0);
+        tree.entries.push(Entry {
+            parent: 0,
+            depth: root.depth(),
+            dir: None,
+        });
+        tree
+    }
+
+    /// Stores `child`, reached from `parent` by its last move, and gives it
+    /// its own index.
+    ///
+    /// # Panics
+    /// Panics if the tree already holds `NodeId::MAX` nodes.
+    pub(crate) fn add(&mut self, parent: &Node, child: &mut Node) {
+        let id = NodeId::try_from(self.entries.len()).expect("Search tree is full");
+        child.set_id(id);
+        self.entries.push(Entry {
+            parent: parent.id(),
+            depth: child.depth(),
+            dir: child.next_move(),
+        });
+    }
+
+    /// Depth of the node stored under `id`.
+    pub(crate) fn depth(&self, id: NodeId) -> u16 {
+        self.entries[id as usize].depth
+    }
+
+    /// Last move made to reach the node stored under `id`, `None` for the root.
+    pub(crate) fn last_move(&self, id: NodeId) -> Option<Dir> {
+        self.entries[id as usize].dir
+    }
+
+    /// Moves leading from the root to the node stored under `id`.
+    pub(crate) fn path(&self, id: NodeId) -> Vec<Dir> {
+        let mut path = Vec::with_capacity(self.depth(id) as usize);
+        let mut entry = self.entries[id as usize];
+
+        while let Some(dir) = entry.dir {
+            path.push(dir);
+            entry = self.entries[entry.parent as usize];
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchTree;
+    use crate::core::{Dir, Shape, node::Node};
+
+    // Test if the path of a node is rebuilt in the order the moves were made
+    #[test]
+    fn test_path() {
+        let mut root = Node::new(Node::default_board(Shape::square(3))).unwrap();
+        let mut tree = SearchTree::new(&mut root);
+
+        let mut up = root.get_node_for_move(Dir::Up).unwrap();
+        tree.add(&root, &mut up);
+        let mut left = root.get_node_for_move(Dir::Left).unwrap();
+        tree.add(&root, &mut left);
+        let mut up_left = up.get_node_for_move(Dir::Left).unwrap();
+        tree.add(&up, &mut up_left);
+
+        assert_eq!(tree.path(root.id()), vec![]);
+        assert_eq!(tree.path(left.id()), vec![Dir::Left]);
+        assert_eq!(tree.path(up_left.id()), vec![Dir::Up, Dir::Left]);
+        assert_eq!(tree.depth(up_left.id()), 2);
+        assert_eq!(tree.last_move(up_left.id()), Some(Dir::Left));
+        assert_eq!(tree.last_move(root.id()), None);
+    }
+}
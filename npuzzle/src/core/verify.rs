@@ -0,0 +1,174 @@
+use std::{error, fmt};
+
+use super::{Dir, MoveError, Problem, node::Node};
+
+/// Replays `path` on the board of `problem` and checks that it solves it,
+/// independently of the search that found it.
+///
+/// # Arguments
+/// * `problem` - The puzzle the path was found for, with its goal
+/// * `path` - Moves of the blank, in order
+/// * `lower_bound` - Known length of an optimal solution, or a lower bound
+///   on it, to also check that the path is optimal
+///
+/// # Returns
+/// * `Ok(())` if every move is legal, the last board is the goal and the
+///   path is no longer than `lower_bound`
+/// * `Err` with the first problem found otherwise
+pub fn verify(
+    problem: &Problem,
+    path: &[Dir],
+    lower_bound: Option<usize>,
+) -> Result<(), VerifyError> {
+    let start = Node::new(problem.get()).map_err(VerifyError::InvalidBoard)?;
+    let end = start.try_follow(path)?;
+
+    if !end.is_solved(&problem.goal()) {
+        return Err(VerifyError::NotSolved(end.to_string()));
+    }
+
+    match lower_bound {
+        Some(bound) if path.len() > bound => Err(VerifyError::NotOptimal {
+            length: path.len(),
+            lower_bound: bound,
+        }),
+        Some(bound) if path.len() < bound => Err(VerifyError::BelowLowerBound {
+            length: path.len(),
+            lower_bound: bound,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Reason why a path does not solve a problem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerifyError {
+    /// The board of the problem is not a valid puzzle state.
+    InvalidBoard(String),
+    /// A move of the path would take the blank off the board.
+    IllegalMove(MoveError),
+    /// The path ends on this board, which is not the goal.
+    NotSolved(String),
+    /// The path is longer than the lower bound, so not optimal.
+    NotOptimal { length: usize, lower_bound: usize },
+    /// The path is shorter than the lower bound, so the bound is wrong.
+    BelowLowerBound { length: usize, lower_bound: usize },
+}
+
+impl From<MoveError> for VerifyError {
+    fn from(err: MoveError) -> Self {
+        VerifyError::IllegalMove(err)
+    }
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::InvalidBoard(reason) => write!(f, "Invalid board: {}", reason),
+            VerifyError::IllegalMove(err) => write!(f, "{}", err),
+            VerifyError::NotSolved(board) => {
+                write!(f, "The path does not reach the goal, it ends on\n{}", board)
+            }
+            VerifyError::NotOptimal {
+                length,
+                lower_bound,
+            } => write!(
+                f,
+                "The path takes {} moves, more than the lower bound of {}",
+                length, lower_bound
+            ),
+            VerifyError::BelowLowerBound {
+                length,
+                lower_bound,
+            } => write!(
+                f,
+                "The path takes {} moves, fewer than the lower bound of {}",
+                length, lower_bound
+            ),
+        }
+    }
+}
+
+impl error::Error for VerifyError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            VerifyError::IllegalMove(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VerifyError, verify};
+    use crate::core::{
+        Dir, Goal, MoveError, Moves, Problem, SearchConfig, Shape, solver::AStarSolver,
+    };
+    use crate::heuristics::Manhattan;
+
+    fn problem() -> Problem {
+        Problem::new(vec![vec![1, 2, 3], vec![4, 0, 6], vec![7, 5, 8]])
+    }
+
+    // Test if a correct path passes, with and without its optimal length
+    #[test]
+    fn test_valid_path() {
+        let path: Moves = "DR".parse().unwrap();
+
+        assert_eq!(verify(&problem(), &path, None), Ok(()));
+        assert_eq!(verify(&problem(), &path, Some(2)), Ok(()));
+    }
+
+    // Test if a path found by a solver passes for a custom goal
+    #[test]
+    fn test_solver_path_with_custom_goal() {
+        let goal = Goal::spiral(Shape::square(3));
+        let start: Moves = "LURRDDLLUR".parse().unwrap();
+        let problem = Problem::with_goal(goal.table(), goal)
+            .apply(&start.to_string())
+            .unwrap();
+
+        let solution = AStarSolver::new(SearchConfig::informed(Manhattan))
+            .unwrap()
+            .solve(problem.clone())
+            .unwrap();
+        let optimal = solution.result_len as usize;
+
+        assert_eq!(verify(&problem, &solution.path, Some(optimal)), Ok(()));
+        assert_eq!(verify(&problem, &start.inverse(), None), Ok(()));
+    }
+
+    // Test if illegal moves, unsolved boards and wrong lengths are reported
+    #[test]
+    fn test_invalid_paths() {
+        assert_eq!(
+            verify(&problem(), &[Dir::Down, Dir::Down, Dir::Right], None),
+            Err(VerifyError::IllegalMove(MoveError::IllegalMove {
+                index: 1,
+                dir: Dir::Down
+            }))
+        );
+        assert!(matches!(
+            verify(&problem(), &[Dir::Down], None),
+            Err(VerifyError::NotSolved(_))
+        ));
+
+        let detour: Moves = "DRLR".parse().unwrap();
+        assert_eq!(verify(&problem(), &detour, None), Ok(()));
+        assert_eq!(
+            verify(&problem(), &detour, Some(2)),
+            Err(VerifyError::NotOptimal {
+                length: 4,
+                lower_bound: 2
+            })
+        );
+        assert!(matches!(
+            verify(&problem(), &detour, Some(6)),
+            Err(VerifyError::BelowLowerBound { .. })
+        ));
+        assert!(matches!(
+            verify(&Problem::new(vec![vec![1, 1], vec![2, 0]]), &[], None),
+            Err(VerifyError::InvalidBoard(_))
+        ));
+    }
+}
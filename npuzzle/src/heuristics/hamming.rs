@@ -0,0 +1,62 @@
+use super::{Heuristic, Slide};
+use crate::core::{Goal, Tile};
+
+/// Number of cells not on its correct position.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hamming;
+
+impl Heuristic for Hamming {
+    fn name(&self) -> &str {
+        "hamm"
+    }
+
+    fn is_admissible(&self) -> bool {
+        // Every misplaced tile has to move at least once
+        true
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        board
+            .iter()
+            .enumerate()
+            .filter(|&(i, v)| is_misplaced(*v, i, goal))
+            .count()
+    }
+
+    fn update(&self, _board: &[Tile], goal: &Goal, estimate: usize, slide: Slide) -> Option<usize> {
+        Some(
+            estimate + is_misplaced(slide.tile, slide.to, goal) as usize
+                - is_misplaced(slide.tile, slide.from, goal) as usize,
+        )
+    }
+}
+
+fn is_misplaced(tile: Tile, index: usize, goal: &Goal) -> bool {
+    tile != 0 && goal.position(tile) != index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Hamming;
+    use crate::core::{Goal, Shape};
+    use crate::heuristics::Heuristic;
+
+    // Test if tiles are compared against their place in the goal, not their number
+    #[test]
+    fn test_counts_tiles_away_from_goal() {
+        let shape = Shape::square(3);
+        let board = [1, 2, 3, 4, 5, 6, 7, 0, 8];
+
+        assert_eq!(Hamming.estimate(&board, &Goal::standard(shape)), 1);
+        assert_eq!(
+            Hamming.estimate(&[1, 2, 3, 4, 5, 6, 7, 8, 0], &Goal::standard(shape)),
+            0
+        );
+        assert_eq!(
+            Hamming.estimate(&[0, 1, 2, 3, 4, 5, 6, 7, 8], &Goal::blank_first(shape)),
+            0
+        );
+        // Only tile 8 is where the blank-first goal wants it
+        assert_eq!(Hamming.estimate(&board, &Goal::blank_first(shape)), 7);
+    }
+}
@@ -0,0 +1,241 @@
+use super::{Heuristic, Manhattan, Slide, manhattan::distance};
+use crate::core::{Goal, Tile};
+
+/// Manhattan distance plus two moves for every tile that has to leave its
+/// line to let another tile of the same line pass.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinearConflict;
+
+impl Heuristic for LinearConflict {
+    fn name(&self) -> &str {
+        "lcon"
+    }
+
+    fn is_admissible(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        Manhattan.estimate(board, goal) + linear_conflict(board, goal)
+    }
+
+    /// Counts again only the conflicts of the two lines the tile leaves and
+    /// enters, before and after the move, without allocating.
+    fn update(&self, board: &[Tile], goal: &Goal, estimate: usize, slide: Slide) -> Option<usize> {
+        let shape = goal.shape();
+        let manhattan = distance(slide.tile, slide.to, goal) as isize
+            - distance(slide.tile, slide.from, goal) as isize;
+
+        // The order of tiles only changes in the lines the tile leaves and
+        // enters, a vertical move keeps its column and a horizontal one its row
+        let lines = if shape.row(slide.from) == shape.row(slide.to) {
+            [
+                Line::Col(shape.col(slide.from)),
+                Line::Col(shape.col(slide.to)),
+            ]
+        } else {
+            [
+                Line::Row(shape.row(slide.from)),
+                Line::Row(shape.row(slide.to)),
+            ]
+        };
+        let after = |index: usize| match index {
+            i if i == slide.from => 0,
+            i if i == slide.to => slide.tile,
+            i => board[i],
+        };
+
+        let mut conflict = 0;
+        for line in lines {
+            conflict += 2 * line.removed(goal, after) as isize;
+            conflict -= 2 * line.removed(goal, |index| board[index]) as isize;
+        }
+
+        estimate.checked_add_signed(manhattan + conflict)
+    }
+}
+
+// Longest side of a board, so the goal positions along a line fit in a byte
+const MAX_LINE: usize = u8::MAX as usize;
+
+// A row or a column of the board
+#[derive(Clone, Copy)]
+enum Line {
+    Row(usize),
+    Col(usize),
+}
+
+impl Line {
+    // Number of tiles of the line that have to step aside to let the other
+    // tiles belonging to the line pass, `tile_at` giving the tile at an index
+    fn removed(self, goal: &Goal, tile_at: impl Fn(usize) -> Tile) -> usize {
+        let shape = goal.shape();
+        let width = shape.width as usize;
+        let len = match self {
+            Line::Row(_) => width,
+            Line::Col(_) => shape.height as usize,
+        };
+
+        // Goal positions along the line of the tiles that belong to it
+        let mut goals = [0u8; MAX_LINE];
+        let mut count = 0;
+        for i in 0..len {
+            let tile = match self {
+                Line::Row(row) => tile_at(row * width + i),
+                Line::Col(col) => tile_at(i * width + col),
+            };
+            if tile == 0 {
+                continue;
+            }
+
+            let target = goal.position(tile);
+            let along = match self {
+                Line::Row(row) => (shape.row(target) == row).then_some(shape.col(target)),
+                Line::Col(col) => (shape.col(target) == col).then_some(shape.row(target)),
+            };
+            if let Some(along) = along {
+                goals[count] = along as u8;
+                count += 1;
+            }
+        }
+
+        // Tiles outside the longest ordered subsequence have to step aside
+        count - longest_increasing(&goals[..count])
+    }
+}
+
+fn linear_conflict(board: &[Tile], goal: &Goal) -> usize {
+    let shape = goal.shape();
+    let tile_at = |index: usize| board[index];
+
+    let rows: usize = (0..shape.height as usize)
+        .map(|row| Line::Row(row).removed(goal, tile_at))
+        .sum();
+    let cols: usize = (0..shape.width as usize)
+        .map(|col| Line::Col(col).removed(goal, tile_at))
+        .sum();
+
+    2 * (rows + cols)
+}
+
+fn longest_increasing(values: &[u8]) -> usize {
+    // Smallest last value of an increasing subsequence of every length so far
+    let mut tails = [0u8; MAX_LINE];
+    let mut len = 0;
+
+    for &value in values {
+        let at = tails[..len].partition_point(|&tail| tail < value);
+        tails[at] = value;
+        len = len.max(at + 1);
+    }
+
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::Bfs;
+    use crate::core::{Algorithm, Goal, Problem, SearchConfig, Shape, Tile};
+    use crate::heuristics::{Heuristic, Manhattan};
+
+    use super::{LinearConflict, linear_conflict, longest_increasing};
+
+    fn goal() -> Goal {
+        Goal::standard(Shape::square(3))
+    }
+
+    // Test if the longest ordered subsequence skips the tiles out of order
+    #[test]
+    fn test_longest_increasing() {
+        assert_eq!(longest_increasing(&[]), 0);
+        assert_eq!(longest_increasing(&[0, 1, 2, 3]), 4);
+        assert_eq!(longest_increasing(&[3, 2, 1, 0]), 1);
+        assert_eq!(longest_increasing(&[2, 0, 3, 1, 4]), 3);
+
+        let wide: Vec<u8> = (0..u8::MAX).rev().collect();
+        assert_eq!(longest_increasing(&wide), 1);
+    }
+
+    #[test]
+    fn test_linear_conflict_solved_board() {
+        let board = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+        assert_eq!(linear_conflict(&board, &goal()), 0);
+        assert_eq!(LinearConflict.estimate(&board, &goal()), 0);
+    }
+
+    #[test]
+    fn test_linear_conflict_row_and_column() {
+        // 2 and 1 are reversed in the first row, 7 and 4 in the first column
+        let board = [2, 1, 3, 7, 5, 6, 4, 8, 0];
+        assert_eq!(Manhattan.estimate(&board, &goal()), 4);
+        assert_eq!(linear_conflict(&board, &goal()), 4);
+    }
+
+    #[test]
+    fn test_linear_conflict_whole_row_reversed() {
+        // Only one of 3, 2, 1 may stay in the row, the other two have to step aside
+        let board = [3, 2, 1, 4, 5, 6, 7, 8, 0];
+        assert_eq!(linear_conflict(&board, &goal()), 4);
+    }
+
+    #[test]
+    fn test_linear_conflict_rectangular_board() {
+        // Only 2 and 3 stay in order in the first row, 4 and 1 have to step aside
+        let board = [4, 2, 3, 1, 5, 6, 7, 0];
+        let goal = Goal::standard(Shape::new(2, 4));
+
+        assert_eq!(linear_conflict(&board, &goal), 4);
+        assert_eq!(linear_conflict(&[1, 2, 3, 4, 5, 6, 7, 0], &goal), 0);
+    }
+
+    #[test]
+    fn test_linear_conflict_towards_spiral() {
+        let goal = Goal::spiral(Shape::square(3));
+        // 4 and 5 are reversed in the last column of the spiral goal
+        let board = [1, 2, 3, 8, 0, 5, 7, 6, 4];
+
+        assert_eq!(linear_conflict(goal.tiles(), &goal), 0);
+        assert_eq!(linear_conflict(&board, &goal), 2);
+    }
+
+    #[test]
+    fn test_linear_conflict_is_admissible() {
+        let boards = [
+            vec![
+                vec![5, 1, 2, 3],
+                vec![0, 6, 7, 4],
+                vec![9, 10, 11, 8],
+                vec![13, 14, 15, 12],
+            ],
+            vec![
+                vec![1, 2, 3, 4],
+                vec![5, 6, 0, 8],
+                vec![9, 10, 7, 11],
+                vec![13, 14, 15, 12],
+            ],
+            vec![vec![1, 0, 3], vec![5, 2, 6], vec![4, 7, 8]],
+            vec![vec![0, 6, 2, 4], vec![1, 5, 3, 7]],
+            vec![vec![2, 0], vec![1, 3], vec![5, 4]],
+        ];
+        let problems = boards
+            .into_iter()
+            .map(Problem::new)
+            .chain([Problem::with_goal(
+                vec![vec![8, 1, 3], vec![0, 2, 4], vec![7, 6, 5]],
+                Goal::spiral(Shape::square(3)),
+            )]);
+
+        for problem in problems {
+            let goal = problem.goal();
+            let optimal = Bfs::new()
+                .run(problem.clone(), &SearchConfig::default())
+                .unwrap()
+                .depth() as usize;
+            let flat: Vec<Tile> = problem.get().into_iter().flatten().collect();
+            let estimate = LinearConflict.estimate(&flat, &goal);
+
+            assert!(estimate <= optimal, "{estimate} > {optimal}");
+            assert!(estimate >= Manhattan.estimate(&flat, &goal));
+        }
+    }
+}
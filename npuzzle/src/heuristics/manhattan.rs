@@ -0,0 +1,40 @@
+use super::{Heuristic, Slide};
+use crate::core::{Goal, Tile};
+
+/// Sum of distances of every tile from its position in the goal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+    fn name(&self) -> &str {
+        "manh"
+    }
+
+    fn is_admissible(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        board
+            .iter()
+            .enumerate()
+            .filter(|&(_, &value)| value != 0)
+            .map(|(index, &value)| distance(value, index, goal))
+            .sum()
+    }
+
+    fn update(&self, _board: &[Tile], goal: &Goal, estimate: usize, slide: Slide) -> Option<usize> {
+        Some(
+            estimate + distance(slide.tile, slide.to, goal)
+                - distance(slide.tile, slide.from, goal),
+        )
+    }
+}
+
+/// Distance of a non-blank `tile` at `index` from its place in `goal`.
+pub(crate) fn distance(tile: Tile, index: usize, goal: &Goal) -> usize {
+    let shape = goal.shape();
+    let target = goal.position(tile);
+
+    shape.row(target).abs_diff(shape.row(index)) + shape.col(target).abs_diff(shape.col(index))
+}
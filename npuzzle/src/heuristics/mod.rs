@@ -0,0 +1,309 @@
+pub mod hamming;
+pub mod linear_conflict;
+pub mod manhattan;
+pub mod pdb;
+pub mod walking_distance;
+
+use std::sync::Arc;
+
+use crate::core::{Goal, Shape, Tile};
+
+pub use hamming::Hamming;
+pub use linear_conflict::LinearConflict;
+pub use manhattan::Manhattan;
+pub use pdb::PatternDatabase;
+pub use walking_distance::{WalkingDistance, WalkingDistanceTable};
+
+/// A tile sliding into the blank, the only change a move makes to a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slide {
+    pub tile: Tile,
+    /// Index the tile leaves, the blank ends up there.
+    pub from: usize,
+    /// Index of the blank the tile moves to.
+    pub to: usize,
+}
+
+impl Slide {
+    /// The tile at `target` of `board` sliding into the blank at `blank`.
+    pub fn new(board: &[Tile], blank: usize, target: usize) -> Self {
+        Self {
+            tile: board[target],
+            from: target,
+            to: blank,
+        }
+    }
+}
+
+/// Estimate of the number of moves needed to solve a board.
+///
+/// Informed searches such as `AStar` and `IdaStar` accept any implementation,
+/// so downstream crates can plug in their own estimates.
+pub trait Heuristic: Send + Sync {
+    /// Short name used in reports.
+    fn name(&self) -> &str;
+
+    /// Whether the estimate never exceeds the real number of moves.
+    fn is_admissible(&self) -> bool;
+
+    /// Estimated number of moves from `board`, given row by row, to `goal`.
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize;
+
+    /// Estimate of the board `slide` leads to from `board`, derived from
+    /// `estimate`, the value of `board`, without looking at the whole board.
+    ///
+    /// Returns `None` by default, searches then estimate the new board in full.
+    fn update(
+        &self,
+        _board: &[Tile],
+        _goal: &Goal,
+        _estimate: usize,
+        _slide: Slide,
+    ) -> Option<usize> {
+        None
+    }
+
+    /// Whether boards of this shape can be estimated.
+    fn supports_shape(&self, _shape: Shape) -> bool {
+        true
+    }
+
+    /// Whether boards can be estimated towards `goal`, called only for goals
+    /// of a supported shape.
+    fn supports_goal(&self, _goal: &Goal) -> bool {
+        true
+    }
+}
+
+/// Largest of several estimates, admissible if all of them are.
+pub struct MaxOf {
+    name: String,
+    heuristics: Vec<Arc<dyn Heuristic>>,
+}
+
+impl MaxOf {
+    pub fn new(heuristics: Vec<Arc<dyn Heuristic>>) -> Self {
+        Self {
+            name: combined_name("max", &heuristics),
+            heuristics,
+        }
+    }
+}
+
+impl Heuristic for MaxOf {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_admissible(&self) -> bool {
+        self.heuristics.iter().all(|h| h.is_admissible())
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        self.heuristics
+            .iter()
+            .map(|h| h.estimate(board, goal))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn supports_shape(&self, shape: Shape) -> bool {
+        self.heuristics.iter().all(|h| h.supports_shape(shape))
+    }
+
+    fn supports_goal(&self, goal: &Goal) -> bool {
+        self.heuristics.iter().all(|h| h.supports_goal(goal))
+    }
+}
+
+/// Sum of several estimates.
+///
+/// A sum of admissible estimates is in general not admissible, unless each of
+/// them counts moves of a different set of tiles, see `SumOf::disjoint`.
+pub struct SumOf {
+    name: String,
+    heuristics: Vec<Arc<dyn Heuristic>>,
+    disjoint: bool,
+}
+
+impl SumOf {
+    pub fn new(heuristics: Vec<Arc<dyn Heuristic>>) -> Self {
+        Self {
+            name: combined_name("sum", &heuristics),
+            heuristics,
+            disjoint: false,
+        }
+    }
+
+    /// Sum of estimates counting moves of disjoint sets of tiles,
+    /// admissible if all of them are.
+    pub fn disjoint(heuristics: Vec<Arc<dyn Heuristic>>) -> Self {
+        Self {
+            disjoint: true,
+            ..Self::new(heuristics)
+        }
+    }
+}
+
+impl Heuristic for SumOf {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn is_admissible(&self) -> bool {
+        self.disjoint && self.heuristics.iter().all(|h| h.is_admissible())
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        self.heuristics
+            .iter()
+            .map(|h| h.estimate(board, goal))
+            .sum()
+    }
+
+    fn supports_shape(&self, shape: Shape) -> bool {
+        self.heuristics.iter().all(|h| h.supports_shape(shape))
+    }
+
+    fn supports_goal(&self, goal: &Goal) -> bool {
+        self.heuristics.iter().all(|h| h.supports_goal(goal))
+    }
+}
+
+fn combined_name(op: &str, heuristics: &[Arc<dyn Heuristic>]) -> String {
+    let names = heuristics
+        .iter()
+        .map(|h| h.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{}({})", op, names)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Hamming, Heuristic, LinearConflict, Manhattan, MaxOf, Slide, SumOf};
+    use crate::core::{Board, Dir, Goal, Shape, Tile, node::Node};
+
+    fn goal() -> Goal {
+        Goal::standard(Shape::square(3))
+    }
+
+    // Heuristic defined outside of the built-in set
+    struct Constant(usize);
+
+    impl Heuristic for Constant {
+        fn name(&self) -> &str {
+            "constant"
+        }
+
+        fn is_admissible(&self) -> bool {
+            self.0 == 0
+        }
+
+        fn estimate(&self, _board: &[Tile], _goal: &Goal) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_max_of() {
+        let board = [2, 1, 3, 7, 5, 6, 4, 8, 0];
+        let max = MaxOf::new(vec![Arc::new(Manhattan), Arc::new(LinearConflict)]);
+
+        assert_eq!(max.name(), "max(manh, lcon)");
+        assert!(max.is_admissible());
+        assert_eq!(
+            max.estimate(&board, &goal()),
+            LinearConflict.estimate(&board, &goal())
+        );
+    }
+
+    #[test]
+    fn test_sum_of() {
+        let board = [2, 1, 3, 7, 5, 6, 4, 8, 0];
+        let sum = SumOf::new(vec![Arc::new(Manhattan), Arc::new(Constant(3))]);
+
+        assert_eq!(sum.name(), "sum(manh, constant)");
+        assert!(!sum.is_admissible());
+        assert_eq!(
+            sum.estimate(&board, &goal()),
+            Manhattan.estimate(&board, &goal()) + 3
+        );
+    }
+
+    #[test]
+    fn test_disjoint_sum_admissibility() {
+        let admissible = SumOf::disjoint(vec![Arc::new(Manhattan), Arc::new(Constant(0))]);
+        let inadmissible = SumOf::disjoint(vec![Arc::new(Manhattan), Arc::new(Constant(1))]);
+
+        assert!(admissible.is_admissible());
+        assert!(!inadmissible.is_admissible());
+    }
+
+    // Walks a pseudo-random sequence of moves from a shuffled board, checking
+    // every incremental update against estimating the new board in full
+    fn check_updates(heuristic: &dyn Heuristic, goal: &Goal) {
+        let start = Node::new(goal.table()).unwrap();
+        let mut board = Board::new(&start, goal);
+        let mut estimate = heuristic.estimate(board.tiles(), goal);
+        let mut seed: u32 = 12345;
+
+        for _ in 0..2000 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let dir = Dir::values()[(seed >> 16) as usize % 4];
+            let Some(slide) = board.slide(dir) else {
+                continue;
+            };
+
+            let updated = heuristic.update(board.tiles(), goal, estimate, slide);
+            board.apply(dir);
+            estimate = heuristic.estimate(board.tiles(), goal);
+
+            assert_eq!(
+                updated,
+                Some(estimate),
+                "{} on {} after {}",
+                heuristic.name(),
+                goal.shape(),
+                dir
+            );
+        }
+    }
+
+    #[test]
+    fn test_incremental_updates_match_full_estimate() {
+        let shapes = (2..=5).map(Shape::square).chain([
+            Shape::new(2, 4),
+            Shape::new(3, 5),
+            Shape::new(4, 6),
+            Shape::new(5, 2),
+            Shape::square(20),
+        ]);
+
+        for shape in shapes {
+            for goal in [
+                Goal::standard(shape),
+                Goal::blank_first(shape),
+                Goal::spiral(shape),
+            ] {
+                check_updates(&Manhattan, &goal);
+                check_updates(&Hamming, &goal);
+                check_updates(&LinearConflict, &goal);
+            }
+        }
+    }
+
+    #[test]
+    fn test_combinators_estimate_in_full() {
+        let board = [2, 1, 3, 7, 5, 6, 4, 8, 0];
+        let max = MaxOf::new(vec![Arc::new(Manhattan), Arc::new(LinearConflict)]);
+        let slide = Slide::new(&board, 8, 7);
+
+        let estimate = max.estimate(&board, &goal());
+
+        assert_eq!(max.update(&board, &goal(), estimate, slide), None);
+    }
+}
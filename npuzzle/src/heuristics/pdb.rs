@@ -0,0 +1,538 @@
+use std::{fs, io, path::Path};
+
+use super::Heuristic;
+use crate::core::{Goal, Shape, Tile};
+
+const MAGIC: &[u8; 4] = b"NPDB";
+const VERSION: u8 = 1;
+const UNSET: u8 = u8::MAX;
+
+/// Largest number of tiles in a single pattern, so a state fits in a `u64`.
+pub const MAX_PATTERN_SIZE: usize = 7;
+
+// A group of tiles with the cost of placing them for every combination of their positions
+struct Pattern {
+    tiles: Vec<Tile>,
+    table: Vec<u8>,
+}
+
+/// Disjoint additive pattern database.
+///
+/// Every pattern stores the number of moves of its own tiles needed to bring
+/// them to their goal cells, so costs of disjoint patterns can be summed
+/// without overestimating. The costs only hold for the goal it was built for.
+pub struct PatternDatabase {
+    goal: Goal,
+    patterns: Vec<Pattern>,
+}
+
+impl PatternDatabase {
+    /// Default tile partitions: 4-4 for 3x3, 6-6-3 for 4x4 and 5-5-5-5-4 for 5x5.
+    pub fn default_groups(shape: Shape) -> Option<Vec<Vec<Tile>>> {
+        if !shape.is_square() {
+            return None;
+        }
+
+        match shape.width {
+            3 => Some(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]),
+            4 => Some(vec![
+                vec![1, 5, 6, 9, 10, 13],
+                vec![7, 8, 11, 12, 14, 15],
+                vec![2, 3, 4],
+            ]),
+            5 => Some(vec![
+                vec![1, 2, 3, 6, 7],
+                vec![4, 5, 8, 9, 10],
+                vec![11, 12, 16, 17, 21],
+                vec![13, 14, 15, 18, 19],
+                vec![20, 22, 23, 24],
+            ]),
+            _ => None,
+        }
+    }
+
+    /// Builds the database for `goal` by a backward breadth-first search from
+    /// it over the abstract space of every group.
+    ///
+    /// # Arguments
+    /// * `goal` - The goal board, at most 64 cells
+    /// * `groups` - Disjoint groups of tiles, at most `MAX_PATTERN_SIZE` tiles each
+    ///
+    /// # Returns
+    /// * `Err` with a description if the groups are not valid for the board
+    pub fn build(goal: &Goal, groups: &[Vec<Tile>]) -> Result<Self, String> {
+        let shape = goal.shape();
+        let cells = shape.cells();
+        if shape.height < 2 || shape.width < 2 || cells > 64 {
+            return Err(format!("Unsupported board shape: {}", shape));
+        }
+
+        let mut seen = vec![false; cells];
+        for group in groups {
+            if group.is_empty() || group.len() > MAX_PATTERN_SIZE {
+                return Err(format!(
+                    "Pattern must have between 1 and {} tiles",
+                    MAX_PATTERN_SIZE
+                ));
+            }
+
+            for &tile in group {
+                if tile == 0 || tile as usize >= cells {
+                    return Err(format!("Tile {} is not on a {} board", tile, shape));
+                }
+                if seen[tile as usize] {
+                    return Err(format!("Tile {} belongs to more than one pattern", tile));
+                }
+                seen[tile as usize] = true;
+            }
+        }
+
+        let patterns = groups
+            .iter()
+            .map(|tiles| Pattern {
+                tiles: tiles.clone(),
+                table: Self::build_table(goal, tiles),
+            })
+            .collect();
+
+        Ok(Self {
+            goal: goal.clone(),
+            patterns,
+        })
+    }
+
+    fn build_table(goal: &Goal, tiles: &[Tile]) -> Vec<u8> {
+        let shape = goal.shape();
+        let cells = shape.cells();
+        let k = tiles.len();
+        let mut table = vec![UNSET; table_size(cells, k)];
+
+        // Abstract state: positions of the pattern tiles followed by the blank
+        let mut start = [0u8; MAX_PATTERN_SIZE + 1];
+        for (slot, &tile) in tiles.iter().enumerate() {
+            start[slot] = goal.position(tile) as u8;
+        }
+        let occupied = occupancy(&start[..k]);
+        start[k] = region(shape, occupied, goal.position(0)).0 as u8;
+
+        // One bit per (pattern positions, blank region) pair
+        let mut visited = vec![0u64; (table.len() * cells).div_ceil(64)];
+        let mark = |visited: &mut Vec<u64>, state: &[u8]| {
+            let bit = rank(cells, &state[..k]) * cells + state[k] as usize;
+            let fresh = visited[bit / 64] & (1 << (bit % 64)) == 0;
+            visited[bit / 64] |= 1 << (bit % 64);
+            fresh
+        };
+
+        mark(&mut visited, &start);
+        table[rank(cells, &start[..k])] = 0;
+
+        let mut layer = vec![u64::from_le_bytes(start)];
+        let mut depth = 0u8;
+
+        while !layer.is_empty() {
+            let mut next = Vec::new();
+            depth = depth.saturating_add(1);
+
+            for packed in layer {
+                let state = packed.to_le_bytes();
+                let occupied = occupancy(&state[..k]);
+                let (_, reachable) = region(shape, occupied, state[k] as usize);
+
+                // Slide any pattern tile next to the blank region into it
+                for cell in (0..cells).filter(|cell| reachable & (1 << cell) != 0) {
+                    for neighbour in neighbours(shape, cell) {
+                        let Some(slot) = state[..k].iter().position(|&p| p as usize == neighbour)
+                        else {
+                            continue;
+                        };
+
+                        let mut child = state;
+                        child[slot] = cell as u8;
+                        let child_occupied = occupancy(&child[..k]);
+                        child[k] = region(shape, child_occupied, neighbour).0 as u8;
+
+                        if mark(&mut visited, &child) {
+                            let index = rank(cells, &child[..k]);
+                            if table[index] == UNSET {
+                                table[index] = depth;
+                            }
+                            next.push(u64::from_le_bytes(child));
+                        }
+                    }
+                }
+            }
+
+            layer = next;
+        }
+
+        table
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.goal.shape()
+    }
+
+    /// Goal the database was built for.
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    pub fn groups(&self) -> Vec<Vec<Tile>> {
+        self.patterns.iter().map(|p| p.tiles.clone()).collect()
+    }
+
+    /// Serializes the database into its binary file format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::from(MAGIC.as_slice());
+        bytes.push(VERSION);
+        bytes.push(self.shape().height);
+        bytes.push(self.shape().width);
+        // Boards have at most 64 cells, so every tile fits in a byte
+        bytes.extend(self.goal.tiles().iter().map(|&tile| tile as u8));
+        bytes.push(self.patterns.len() as u8);
+
+        for pattern in &self.patterns {
+            bytes.push(pattern.tiles.len() as u8);
+            bytes.extend(pattern.tiles.iter().map(|&tile| tile as u8));
+            bytes.extend_from_slice(&(pattern.table.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&pattern.table);
+        }
+
+        bytes
+    }
+
+    /// Reads a database written by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        let mut reader = bytes;
+        let mut take = |len: usize| -> io::Result<&[u8]> {
+            if reader.len() < len {
+                return Err(invalid("Unexpected end of pattern database"));
+            }
+            let (head, tail) = reader.split_at(len);
+            reader = tail;
+            Ok(head)
+        };
+
+        if take(4)? != MAGIC {
+            return Err(invalid("Not a pattern database file"));
+        }
+        if take(1)?[0] != VERSION {
+            return Err(invalid("Unsupported pattern database version"));
+        }
+
+        let shape = Shape::new(take(1)?[0], take(1)?[0]);
+        let cells = shape.cells();
+        if cells > 64 {
+            return Err(invalid("Unsupported pattern database board"));
+        }
+        let tiles = take(cells)?.chunks(shape.width.max(1) as usize);
+        let goal = Goal::new(tiles.map(widen).collect())
+            .map_err(|_| invalid("Invalid pattern database goal"))?;
+        let count = take(1)?[0] as usize;
+        let mut patterns = Vec::with_capacity(count);
+        // Patterns must not share tiles, or their sum could overestimate
+        let mut seen = vec![false; cells];
+
+        for _ in 0..count {
+            let k = take(1)?[0] as usize;
+            let tiles = widen(take(k)?);
+            if k == 0
+                || k > MAX_PATTERN_SIZE
+                || tiles.iter().any(|&t| t == 0 || t as usize >= cells)
+            {
+                return Err(invalid("Invalid pattern tiles"));
+            }
+            for &tile in &tiles {
+                if std::mem::replace(&mut seen[tile as usize], true) {
+                    return Err(invalid("Tile belongs to more than one pattern"));
+                }
+            }
+
+            let len = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+            if len != table_size(cells, k) {
+                return Err(invalid("Pattern table size does not match the board"));
+            }
+
+            let table = take(len)?.to_vec();
+            patterns.push(Pattern { tiles, table });
+        }
+
+        if !reader.is_empty() {
+            return Err(invalid("Unexpected data after the pattern tables"));
+        }
+
+        Ok(Self { goal, patterns })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_bytes())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+}
+
+impl Heuristic for PatternDatabase {
+    fn name(&self) -> &str {
+        "pdb"
+    }
+
+    fn is_admissible(&self) -> bool {
+        true
+    }
+
+    /// Sum of the pattern costs for the given board.
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        debug_assert_eq!(*goal, self.goal);
+
+        let mut positions = vec![0u8; board.len()];
+        for (index, &tile) in board.iter().enumerate() {
+            positions[tile as usize] = index as u8;
+        }
+
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                let mut state = [0u8; MAX_PATTERN_SIZE];
+                for (slot, &tile) in pattern.tiles.iter().enumerate() {
+                    state[slot] = positions[tile as usize];
+                }
+                pattern.table[rank(board.len(), &state[..pattern.tiles.len()])] as usize
+            })
+            .sum()
+    }
+
+    fn supports_shape(&self, shape: Shape) -> bool {
+        shape == self.shape()
+    }
+
+    fn supports_goal(&self, goal: &Goal) -> bool {
+        *goal == self.goal
+    }
+}
+
+fn widen(bytes: &[u8]) -> Vec<Tile> {
+    bytes.iter().map(|&tile| tile as Tile).collect()
+}
+
+// Number of ways to place `k` distinct tiles on `cells` cells
+fn table_size(cells: usize, k: usize) -> usize {
+    (cells - k + 1..=cells).product()
+}
+
+// Index of a placement of distinct tiles among all placements of that many tiles
+fn rank(cells: usize, positions: &[u8]) -> usize {
+    let mut index = 0;
+
+    for (i, &pos) in positions.iter().enumerate() {
+        let smaller = positions[..i].iter().filter(|&&p| p < pos).count();
+        index = index * (cells - i) + pos as usize - smaller;
+    }
+
+    index
+}
+
+fn occupancy(positions: &[u8]) -> u64 {
+    positions.iter().fold(0, |mask, &p| mask | (1 << p))
+}
+
+fn neighbours(shape: Shape, cell: usize) -> impl Iterator<Item = usize> {
+    let (height, width) = (shape.height as usize, shape.width as usize);
+    let (row, col) = (shape.row(cell), shape.col(cell));
+    [
+        (row > 0).then(|| cell - width),
+        (row + 1 < height).then(|| cell + width),
+        (col > 0).then(|| cell - 1),
+        (col + 1 < width).then(|| cell + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+// Cells the blank can reach without moving a pattern tile, with the lowest of them
+fn region(shape: Shape, occupied: u64, blank: usize) -> (usize, u64) {
+    let (cells, width) = (shape.cells(), shape.width as usize);
+    let board = if cells == 64 {
+        u64::MAX
+    } else {
+        (1 << cells) - 1
+    };
+    let first_col = (0..shape.height as usize).fold(0u64, |mask, row| mask | (1 << (row * width)));
+    let last_col = first_col << (width - 1);
+    let free = board & !occupied;
+
+    let mut reachable = 1u64 << blank;
+    loop {
+        let grown = (reachable
+            | ((reachable << 1) & !first_col)
+            | ((reachable >> 1) & !last_col)
+            | (reachable << width)
+            | (reachable >> width))
+            & free;
+
+        if grown == reachable {
+            return (reachable.trailing_zeros() as usize, reachable);
+        }
+        reachable = grown;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::Bfs;
+    use crate::core::{Algorithm, Goal, Problem, SearchConfig, Shape, Tile};
+    use crate::heuristics::{Heuristic, Manhattan};
+
+    use super::{PatternDatabase, rank, table_size};
+
+    fn database() -> PatternDatabase {
+        let goal = Goal::standard(Shape::square(3));
+        let groups = PatternDatabase::default_groups(goal.shape()).unwrap();
+        PatternDatabase::build(&goal, &groups).unwrap()
+    }
+
+    #[test]
+    fn test_rank_is_a_bijection() {
+        let mut seen = vec![false; table_size(5, 3)];
+        for a in 0..5u8 {
+            for b in (0..5u8).filter(|&b| b != a) {
+                for c in (0..5u8).filter(|&c| c != a && c != b) {
+                    let index = rank(5, &[a, b, c]);
+                    assert!(!seen[index], "Rank {} assigned twice", index);
+                    seen[index] = true;
+                }
+            }
+        }
+        assert!(seen.into_iter().all(|s| s));
+    }
+
+    #[test]
+    fn test_goal_has_zero_cost() {
+        let pdb = database();
+        assert_eq!(pdb.estimate(&[1, 2, 3, 4, 5, 6, 7, 8, 0], pdb.goal()), 0);
+    }
+
+    #[test]
+    fn test_estimate_is_admissible_and_dominates_manhattan() {
+        let pdb = database();
+        let boards = [
+            vec![vec![1, 0, 3], vec![5, 2, 6], vec![4, 7, 8]],
+            vec![vec![4, 1, 3], vec![7, 2, 6], vec![0, 5, 8]],
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![0, 7, 8]],
+        ];
+
+        for board in boards {
+            let optimal = Bfs::new()
+                .run(Problem::new(board.clone()), &SearchConfig::default())
+                .unwrap()
+                .depth() as usize;
+            let flat: Vec<Tile> = board.into_iter().flatten().collect();
+            let estimate = pdb.estimate(&flat, pdb.goal());
+
+            assert!(estimate <= optimal, "{} > {}", estimate, optimal);
+            assert!(estimate >= Manhattan.estimate(&flat, pdb.goal()));
+        }
+    }
+
+    #[test]
+    fn test_rectangular_board() {
+        let shape = Shape::new(2, 4);
+        let goal = Goal::standard(shape);
+        let pdb = PatternDatabase::build(&goal, &[vec![1, 2, 5, 6], vec![3, 4, 7]]).unwrap();
+        let board = vec![vec![0, 6, 2, 4], vec![1, 5, 3, 7]];
+
+        let optimal = Bfs::new()
+            .run(Problem::new(board.clone()), &SearchConfig::default())
+            .unwrap()
+            .depth() as usize;
+        let flat: Vec<Tile> = board.into_iter().flatten().collect();
+        let estimate = pdb.estimate(&flat, &goal);
+
+        assert!(estimate <= optimal, "{} > {}", estimate, optimal);
+        assert!(estimate >= Manhattan.estimate(&flat, &goal));
+        assert_eq!(pdb.estimate(&[1, 2, 3, 4, 5, 6, 7, 0], &goal), 0);
+        assert!(PatternDatabase::default_groups(shape).is_none());
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        let goal = Goal::standard(Shape::square(3));
+        assert!(PatternDatabase::build(&goal, &[vec![1, 2], vec![2, 3]]).is_err());
+        assert!(PatternDatabase::build(&goal, &[vec![0, 1]]).is_err());
+        assert!(PatternDatabase::build(&goal, &[vec![9]]).is_err());
+        let large = Goal::standard(Shape::new(8, 9));
+        assert!(PatternDatabase::build(&large, &[vec![1]]).is_err());
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let pdb = database();
+        let loaded = PatternDatabase::from_bytes(&pdb.to_bytes()).unwrap();
+        let board = [4, 1, 3, 7, 2, 6, 0, 5, 8];
+
+        assert_eq!(loaded.shape(), Shape::square(3));
+        assert_eq!(loaded.groups(), pdb.groups());
+        assert_eq!(
+            loaded.estimate(&board, loaded.goal()),
+            pdb.estimate(&board, pdb.goal())
+        );
+    }
+
+    // Test if a database built for a custom goal measures distances to it
+    #[test]
+    fn test_custom_goal() {
+        let goal = Goal::spiral(Shape::square(3));
+        let groups = PatternDatabase::default_groups(goal.shape()).unwrap();
+        let pdb = PatternDatabase::build(&goal, &groups).unwrap();
+        let problem = Problem::with_goal(vec![vec![8, 1, 3], vec![0, 2, 4], vec![7, 6, 5]], goal);
+
+        let optimal = Bfs::new()
+            .run(problem.clone(), &SearchConfig::default())
+            .unwrap()
+            .depth() as usize;
+        let flat: Vec<Tile> = problem.get().into_iter().flatten().collect();
+        let estimate = pdb.estimate(&flat, pdb.goal());
+
+        assert_eq!(pdb.estimate(pdb.goal().tiles(), pdb.goal()), 0);
+        assert!(estimate <= optimal, "{} > {}", estimate, optimal);
+        assert!(estimate >= Manhattan.estimate(&flat, pdb.goal()));
+        assert!(pdb.supports_goal(pdb.goal()));
+        assert!(!pdb.supports_goal(&Goal::standard(Shape::square(3))));
+
+        let loaded = PatternDatabase::from_bytes(&pdb.to_bytes()).unwrap();
+        assert_eq!(loaded.goal(), pdb.goal());
+    }
+
+    #[test]
+    fn test_truncated_bytes() {
+        let bytes = database().to_bytes();
+        assert!(PatternDatabase::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PatternDatabase::from_bytes(b"XXXX").is_err());
+    }
+
+    // Test if files with overlapping patterns or trailing data are rejected
+    #[test]
+    fn test_corrupt_bytes() {
+        let bytes = database().to_bytes();
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(PatternDatabase::from_bytes(&trailing).is_err());
+
+        // Both patterns of a hand-made file hold tile 1
+        let table = PatternDatabase::build(&Goal::standard(Shape::square(3)), &[vec![1]])
+            .unwrap()
+            .to_bytes();
+        let mut overlapping = table.clone();
+        overlapping[16] = 2;
+        overlapping.extend_from_slice(&table[17..]);
+        assert!(PatternDatabase::from_bytes(&overlapping).is_err());
+
+        let mut disjoint = overlapping.clone();
+        disjoint[table.len() + 1] = 2;
+        assert!(PatternDatabase::from_bytes(&disjoint).is_ok());
+    }
+}
@@ -0,0 +1,347 @@
+use std::{
+    collections::{HashMap, VecDeque, hash_map::Entry},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use super::Heuristic;
+use crate::core::{Goal, PackedBoard, Shape, Tile};
+
+/// Largest supported number of rows and columns, so a state fits in a `u128`.
+pub const MAX_DIM: usize = 6;
+
+const BITS: u32 = 3;
+
+/// Walking distance heuristic table for one direction of one board shape.
+///
+/// A state counts, for every line, how many of its tiles belong to each goal
+/// line, together with the line of the blank. The table holds the number of
+/// moves needed to sort such a state. Rows of a board with `h` rows of `w`
+/// tiles use the table of `h` lines of length `w`, its columns the table of
+/// `w` lines of length `h`. Which tiles belong to a line does not matter, only
+/// the goal line of the blank, so most goals share their tables.
+pub struct WalkingDistanceTable {
+    lines: usize,
+    length: usize,
+    blank: usize,
+    table: HashMap<u128, u8>,
+}
+
+impl WalkingDistanceTable {
+    /// Builds the table for `lines` lines of `length` tiles, the blank
+    /// belonging to line `blank`, by a breadth-first search from the goal state.
+    ///
+    /// # Panics
+    /// Panics if `lines` or `length` is not between 2 and `MAX_DIM`, or
+    /// `blank` is not one of the lines.
+    pub fn new(lines: usize, length: usize, blank: usize) -> Self {
+        assert!(
+            (2..=MAX_DIM).contains(&lines) && (2..=MAX_DIM).contains(&length),
+            "Walking distance supports boards from 2x2 to {}x{}",
+            MAX_DIM,
+            MAX_DIM
+        );
+        assert!(blank < lines, "Blank must belong to one of the lines");
+
+        let mut goal = vec![0u8; lines * lines];
+        for line in 0..lines {
+            goal[line * lines + line] = length as u8;
+        }
+        goal[blank * lines + blank] -= 1;
+
+        let start = encode(&goal, blank);
+        let mut table = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(key) = queue.pop_front() {
+            let distance = table[&key];
+            let (counts, blank) = decode(key, lines);
+
+            // A tile from a neighbouring line moves into the blank's line
+            let adjacent = [blank.checked_sub(1), Some(blank + 1).filter(|&l| l < lines)];
+            for from in adjacent.into_iter().flatten() {
+                for group in 0..lines {
+                    if counts[from * lines + group] == 0 {
+                        continue;
+                    }
+
+                    let mut next = counts.clone();
+                    next[from * lines + group] -= 1;
+                    next[blank * lines + group] += 1;
+
+                    let next_key = encode(&next, from);
+                    if let Entry::Vacant(entry) = table.entry(next_key) {
+                        entry.insert(distance + 1);
+                        queue.push_back(next_key);
+                    }
+                }
+            }
+        }
+
+        Self {
+            lines,
+            length,
+            blank,
+            table,
+        }
+    }
+
+    /// Shared table for `lines` lines of `length` tiles, the blank belonging
+    /// to line `blank`, built on first use.
+    pub fn for_lines(lines: usize, length: usize, blank: usize) -> Arc<Self> {
+        type Cache = Mutex<HashMap<(usize, usize, usize), Arc<WalkingDistanceTable>>>;
+        static CACHE: OnceLock<Cache> = OnceLock::new();
+
+        let mut cache = CACHE.get_or_init(Default::default).lock().unwrap();
+        cache
+            .entry((lines, length, blank))
+            .or_insert_with(|| Arc::new(Self::new(lines, length, blank)))
+            .clone()
+    }
+
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Line the blank belongs to in the goal.
+    pub fn blank(&self) -> usize {
+        self.blank
+    }
+
+    /// Number of distinct line states reachable from the goal.
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Number of moves needed to sort `counts`, holding for every line how
+    /// many of its tiles belong to each goal line, with the blank in `blank`.
+    ///
+    /// # Panics
+    /// Panics if the state is not in the table, which holds every state of a
+    /// board with the blank of the goal, so the counts are not of such a board.
+    pub fn cost(&self, counts: &[u8], blank: usize) -> usize {
+        *self
+            .table
+            .get(&encode(counts, blank))
+            .expect("Walking distance table holds every state of the board") as usize
+    }
+}
+
+// The tables of rows and columns of one goal
+struct Tables {
+    goal: Goal,
+    rows: Arc<WalkingDistanceTable>,
+    cols: Arc<WalkingDistanceTable>,
+}
+
+impl Tables {
+    fn new(goal: &Goal) -> Self {
+        let shape = goal.shape();
+        let (height, width) = (shape.height as usize, shape.width as usize);
+        let blank = goal.position(0);
+
+        Self {
+            goal: goal.clone(),
+            rows: WalkingDistanceTable::for_lines(height, width, shape.row(blank)),
+            cols: WalkingDistanceTable::for_lines(width, height, shape.col(blank)),
+        }
+    }
+
+    // Sum of the vertical and horizontal walking distances of the board
+    fn estimate(&self, board: &[Tile]) -> usize {
+        let shape = self.goal.shape();
+        let (height, width) = (shape.height as usize, shape.width as usize);
+        debug_assert_eq!(board.len(), shape.cells());
+
+        let mut rows = vec![0u8; height * height];
+        let mut cols = vec![0u8; width * width];
+        let mut blank = 0;
+
+        for (index, &tile) in board.iter().enumerate() {
+            if tile == 0 {
+                blank = index;
+                continue;
+            }
+
+            let target = self.goal.position(tile);
+            rows[shape.row(index) * height + shape.row(target)] += 1;
+            cols[shape.col(index) * width + shape.col(target)] += 1;
+        }
+
+        self.rows.cost(&rows, shape.row(blank)) + self.cols.cost(&cols, shape.col(blank))
+    }
+}
+
+/// Walking distance heuristic, keeping the tables of every goal it is asked
+/// about. The tables of the first goal are read without locking.
+#[derive(Default)]
+pub struct WalkingDistance {
+    first: OnceLock<Tables>,
+    others: Mutex<HashMap<(Shape, PackedBoard), Arc<Tables>>>,
+}
+
+impl Heuristic for WalkingDistance {
+    fn name(&self) -> &str {
+        "walk"
+    }
+
+    fn is_admissible(&self) -> bool {
+        true
+    }
+
+    fn estimate(&self, board: &[Tile], goal: &Goal) -> usize {
+        let first = self.first.get_or_init(|| Tables::new(goal));
+        if first.goal == *goal {
+            return first.estimate(board);
+        }
+
+        let tables = self
+            .others
+            .lock()
+            .unwrap()
+            .entry((goal.shape(), goal.key().clone()))
+            .or_insert_with(|| Arc::new(Tables::new(goal)))
+            .clone();
+        tables.estimate(board)
+    }
+
+    fn supports_shape(&self, shape: Shape) -> bool {
+        (2..=MAX_DIM).contains(&(shape.height as usize))
+            && (2..=MAX_DIM).contains(&(shape.width as usize))
+    }
+}
+
+fn encode(counts: &[u8], blank: usize) -> u128 {
+    counts
+        .iter()
+        .fold(blank as u128, |key, &count| (key << BITS) | count as u128)
+}
+
+fn decode(mut key: u128, lines: usize) -> (Vec<u8>, usize) {
+    let mut counts = vec![0u8; lines * lines];
+
+    for count in counts.iter_mut().rev() {
+        *count = (key & ((1 << BITS) - 1)) as u8;
+        key >>= BITS;
+    }
+
+    (counts, key as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::Bfs;
+    use crate::core::{Algorithm, Goal, Problem, SearchConfig, Shape, Tile};
+    use crate::heuristics::{Heuristic, Manhattan};
+
+    use super::{WalkingDistance, WalkingDistanceTable, decode, encode};
+
+    // Test if a packed table state unpacks to the same counts and blank line
+    #[test]
+    fn test_encode_decode() {
+        let counts = vec![3, 0, 1, 0, 3, 1, 0, 0, 2];
+        assert_eq!(decode(encode(&counts, 2), 3), (counts, 2));
+    }
+
+    // Test if the tables reach every walking distance state
+    #[test]
+    fn test_table_sizes() {
+        // Known number of walking distance states of the 15-puzzle
+        assert_eq!(WalkingDistanceTable::for_lines(4, 4, 3).len(), 24964);
+        // Blank belonging to the first line mirrors the one in the last
+        assert_eq!(WalkingDistanceTable::for_lines(4, 4, 0).len(), 24964);
+        assert!(!WalkingDistanceTable::for_lines(3, 3, 1).is_empty());
+        assert!(!WalkingDistanceTable::for_lines(2, 4, 1).is_empty());
+    }
+
+    // Test if goal boards of every shape and layout cost nothing
+    #[test]
+    fn test_goal_has_zero_cost() {
+        let wd = WalkingDistance::default();
+        let goals = [
+            Goal::standard(Shape::square(3)),
+            Goal::standard(Shape::new(2, 4)),
+            Goal::standard(Shape::new(4, 2)),
+            Goal::spiral(Shape::square(4)),
+            Goal::blank_first(Shape::new(3, 2)),
+        ];
+
+        for goal in goals {
+            assert_eq!(wd.estimate(goal.tiles(), &goal), 0);
+        }
+    }
+
+    // Test if the estimate never exceeds the optimal cost and never falls below Manhattan
+    #[test]
+    fn test_estimate_is_admissible_and_dominates_manhattan() {
+        let wd = WalkingDistance::default();
+        let boards = [
+            vec![vec![1, 0, 3], vec![5, 2, 6], vec![4, 7, 8]],
+            vec![vec![4, 1, 3], vec![7, 2, 6], vec![0, 5, 8]],
+            vec![
+                vec![5, 1, 2, 3],
+                vec![0, 6, 7, 4],
+                vec![9, 10, 11, 8],
+                vec![13, 14, 15, 12],
+            ],
+            vec![vec![0, 6, 2, 4], vec![1, 5, 3, 7]],
+            vec![vec![2, 0], vec![1, 3], vec![5, 4]],
+        ];
+        let problems = boards.into_iter().map(Problem::new).chain([
+            Problem::with_goal(
+                vec![vec![8, 1, 3], vec![0, 2, 4], vec![7, 6, 5]],
+                Goal::spiral(Shape::square(3)),
+            ),
+            Problem::with_goal(
+                vec![vec![1, 2, 5], vec![3, 0, 4]],
+                Goal::blank_first(Shape::new(2, 3)),
+            ),
+        ]);
+
+        for problem in problems {
+            let goal = problem.goal();
+            let optimal = Bfs::new()
+                .run(problem.clone(), &SearchConfig::default())
+                .unwrap()
+                .depth() as usize;
+            let flat: Vec<Tile> = problem.get().into_iter().flatten().collect();
+            let estimate = wd.estimate(&flat, &goal);
+
+            assert!(estimate <= optimal, "{} > {}", estimate, optimal);
+            assert!(estimate >= Manhattan.estimate(&flat, &goal));
+        }
+    }
+
+    // Test if the tables of goals after the first are built once and kept
+    #[test]
+    fn test_tables_kept_per_goal() {
+        let wd = WalkingDistance::default();
+        let standard = Goal::standard(Shape::square(3));
+        let spiral = Goal::spiral(Shape::square(3));
+        let board = [8, 1, 3, 0, 2, 4, 7, 6, 5];
+
+        wd.estimate(&board, &standard);
+        let estimate = wd.estimate(&board, &spiral);
+        wd.estimate(&board, &spiral);
+
+        assert_eq!(wd.others.lock().unwrap().len(), 1);
+        assert_eq!(
+            estimate,
+            WalkingDistance::default().estimate(&board, &spiral)
+        );
+    }
+
+    // Test if tables too large to build are refused
+    #[test]
+    #[should_panic]
+    fn test_unsupported_dim() {
+        WalkingDistanceTable::new(7, 7, 6);
+    }
+}
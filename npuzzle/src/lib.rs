@@ -1,12 +1,22 @@
 pub mod algorithms;
 pub mod core;
+pub mod heuristics;
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use crate::core::Algorithm;
     use crate::core::{
-        Dir, Problem,
-        order::Order,
-        solver::{AStarSolver, BfsSolver, DfsSolver},
+        CancelToken, ConfigError, Dir, Goal, Limit, Problem, SearchConfig, Shape, SolveError,
+        node::Node,
+        solver::{AStarSolver, BfsSolver, BiBfsSolver, DfsSolver, IdaStarSolver},
+    };
+    use crate::heuristics::{
+        Hamming, Heuristic, LinearConflict, Manhattan, PatternDatabase, WalkingDistance,
     };
 
     #[test]
@@ -18,13 +28,14 @@ mod tests {
             vec![13, 14, 15, 12],
         ]);
 
-        let mut solver = DfsSolver::new(Order::Perm(Dir::values()));
+        let config = SearchConfig::builder().max_depth(20).build().unwrap();
+        let mut solver = DfsSolver::new(config).unwrap();
         let solution = solver.solve(unsolved_board.clone());
 
         println!("solution: {:?}", solution.as_ref().unwrap());
 
         // Ensure a result is found
-        assert!(solution.is_some(), "Solver should find a solution");
+        assert!(solution.is_ok(), "Solver should find a solution");
 
         // Ensure result length is valid (non-zero if solved)
         assert!(
@@ -42,7 +53,8 @@ mod tests {
             vec![9, 13, 15, 12],
         ]);
 
-        let mut solver = DfsSolver::new(Order::Perm(Dir::values()));
+        let config = SearchConfig::builder().max_depth(20).build().unwrap();
+        let mut solver = DfsSolver::new(config).unwrap();
         let solution = solver.solve(unsolved_board.clone()).unwrap();
 
         // Check that performance metrics are being recorded
@@ -69,13 +81,13 @@ mod tests {
             vec![13, 14, 15, 12],
         ]);
 
-        let mut solver = AStarSolver::new(Order::Manh);
+        let mut solver = AStarSolver::new(SearchConfig::informed(Manhattan)).unwrap();
 
         let solution = solver.solve(unsolved_board.clone());
         println!("solution: {:?}", solution.as_ref().unwrap());
 
         // Ensure a result is found
-        assert!(solution.is_some(), "Solver should find a solution");
+        assert!(solution.is_ok(), "Solver should find a solution");
 
         // Ensure result length is valid (non-zero if solved)
         assert!(
@@ -93,7 +105,7 @@ mod tests {
             vec![13, 14, 15, 12],
         ]);
 
-        let mut solver = BfsSolver::new(Order::Perm(Dir::values()));
+        let mut solver = BfsSolver::new(SearchConfig::default()).unwrap();
         let solution = solver.solve(unsolved_board.clone()).unwrap();
 
         // Check that performance metrics are being recorded
@@ -110,4 +122,402 @@ mod tests {
             "Reached depth should be non-negative"
         );
     }
+
+    #[test]
+    fn test_idastar_solver_reports_iterations() {
+        let unsolved_board = Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 0, 8],
+            vec![9, 10, 7, 11],
+            vec![13, 14, 15, 12],
+        ]);
+
+        let mut solver = IdaStarSolver::new(SearchConfig::informed(Manhattan)).unwrap();
+        let solution = solver.solve(unsolved_board).unwrap();
+
+        assert_eq!(solution.result_len, 3, "Solution should be optimal");
+        assert_eq!(solution.path.len(), solution.result_len as usize);
+        assert!(
+            !solution.iterations.is_empty(),
+            "Threshold passes should be reported"
+        );
+    }
+
+    #[test]
+    fn test_solver_rejects_unsolvable_board() {
+        let unsolvable_board = Problem::new(vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12],
+            vec![13, 15, 14, 0],
+        ]);
+
+        let mut solver = BfsSolver::new(SearchConfig::default()).unwrap();
+        let result = solver.solve(unsolvable_board);
+
+        assert!(matches!(result, Err(SolveError::Unsolvable)));
+        assert_eq!(
+            solver.algorithm.get_processed_count(),
+            0,
+            "Search should not be started"
+        );
+    }
+
+    #[test]
+    fn test_solver_reports_invalid_board() {
+        let invalid_board = Problem::new(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 8]]);
+
+        let mut solver = BfsSolver::new(SearchConfig::default()).unwrap();
+        let result = solver.solve(invalid_board);
+
+        assert!(matches!(result, Err(SolveError::InvalidBoard(_))));
+    }
+
+    #[test]
+    fn test_solver_reports_depth_limit_with_stats() {
+        let unsolved_board = Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ]);
+
+        let config = SearchConfig::builder().max_depth(2).build().unwrap();
+        let mut solver = BfsSolver::new(config).unwrap();
+        let err = solver.solve(unsolved_board).unwrap_err();
+
+        assert!(matches!(err, SolveError::DepthLimitReached(_)));
+        assert!(err.is_limit());
+        let stats = err.stats().unwrap();
+        assert!(
+            stats.processed_count > 0,
+            "Partial statistics should be kept"
+        );
+        assert!(stats.path.is_empty());
+    }
+
+    #[test]
+    fn test_solver_reports_unsupported_heuristic() {
+        let wide_board = Problem::new(Node::default_board(Shape::new(3, 7)));
+
+        let config = SearchConfig::informed(WalkingDistance::default());
+        let mut solver = AStarSolver::new(config).unwrap();
+        let result = solver.solve(wide_board);
+
+        assert!(matches!(
+            result,
+            Err(SolveError::UnsupportedConfig(ConfigError::UnsupportedShape(shape)))
+                if shape == Shape::new(3, 7)
+        ));
+    }
+
+    fn seven_move_board() -> Problem {
+        Problem::new(vec![
+            vec![5, 1, 2, 3],
+            vec![0, 6, 7, 4],
+            vec![9, 10, 11, 8],
+            vec![13, 14, 15, 12],
+        ])
+    }
+
+    #[test]
+    fn test_solver_reports_node_limit() {
+        let config = SearchConfig::builder()
+            .heuristic(Manhattan)
+            .max_nodes(2)
+            .build()
+            .unwrap();
+        let mut solver = AStarSolver::new(config).unwrap();
+        let err = solver.solve(seven_move_board()).unwrap_err();
+
+        assert!(matches!(err, SolveError::NodeLimitReached(_)));
+        assert_eq!(err.limit(), Some(Limit::Nodes));
+        assert!(err.stats().unwrap().processed_count <= 3);
+    }
+
+    #[test]
+    fn test_solver_reports_timeout() {
+        let config = SearchConfig::builder()
+            .heuristic(Manhattan)
+            .timeout(Duration::ZERO)
+            .build()
+            .unwrap();
+        let mut solver = IdaStarSolver::new(config).unwrap();
+        let err = solver.solve(seven_move_board()).unwrap_err();
+
+        assert!(matches!(err, SolveError::Timeout(_)));
+        assert_eq!(err.limit(), Some(Limit::Time));
+    }
+
+    #[test]
+    fn test_solver_reports_memory_limit() {
+        let config = SearchConfig::builder().max_memory(4096).build().unwrap();
+        let mut solver = BfsSolver::new(config).unwrap();
+        let err = solver.solve(seven_move_board()).unwrap_err();
+
+        assert!(matches!(err, SolveError::MemoryLimitReached(_)));
+        assert!(err.is_limit());
+    }
+
+    #[test]
+    fn test_depth_limit_beyond_u8() {
+        let config = SearchConfig::builder()
+            .heuristic(Manhattan)
+            .max_depth(300)
+            .build()
+            .unwrap();
+        let mut solver = AStarSolver::new(config).unwrap();
+
+        let solution = solver.solve(seven_move_board()).unwrap();
+        assert_eq!(solution.result_len, 7);
+    }
+
+    #[test]
+    fn test_cancelled_token_stops_every_solver() {
+        let token = CancelToken::new();
+        token.cancel();
+        let config = SearchConfig::builder()
+            .cancel_token(token.clone())
+            .build()
+            .unwrap();
+        let informed = SearchConfig::builder()
+            .heuristic(Manhattan)
+            .cancel_token(token)
+            .build()
+            .unwrap();
+
+        let results = [
+            BfsSolver::new(config.clone())
+                .unwrap()
+                .solve(seven_move_board()),
+            DfsSolver::new(config).unwrap().solve(seven_move_board()),
+            AStarSolver::new(informed)
+                .unwrap()
+                .solve(seven_move_board()),
+        ];
+
+        for result in results {
+            let err = result.unwrap_err();
+            assert!(matches!(err, SolveError::Cancelled(_)));
+            assert!(err.stats().unwrap().processed_count <= 1);
+        }
+    }
+
+    #[test]
+    fn test_cancel_running_search_from_another_thread() {
+        // Far beyond the reach of BFS within the test
+        let hard_board = Problem::new(vec![
+            vec![14, 15, 8, 12],
+            vec![10, 11, 9, 13],
+            vec![2, 6, 5, 1],
+            vec![3, 7, 4, 0],
+        ]);
+        let token = CancelToken::new();
+        let config = SearchConfig::builder()
+            .max_depth(u16::MAX)
+            .cancel_token(token.clone())
+            .build()
+            .unwrap();
+        let mut solver = BfsSolver::new(config).unwrap();
+
+        let search = thread::spawn(move || {
+            let start = Instant::now();
+            (solver.solve(hard_board), start.elapsed())
+        });
+        thread::sleep(Duration::from_millis(50));
+        token.cancel();
+        let (result, elapsed) = search.join().unwrap();
+
+        let err = result.unwrap_err();
+        assert!(matches!(err, SolveError::Cancelled(_)));
+        assert!(err.stats().unwrap().visited_count > 0);
+        assert!(elapsed < Duration::from_secs(5));
+    }
+
+    // Test if every kind of solver finds an optimal solution on boards that
+    // are not square, scrambled from the goal by a known number of moves
+    #[test]
+    fn test_rectangular_boards() {
+        let scramble = [
+            Dir::Up,
+            Dir::Left,
+            Dir::Down,
+            Dir::Left,
+            Dir::Up,
+            Dir::Left,
+            Dir::Down,
+            Dir::Right,
+        ];
+
+        for shape in [
+            Shape::new(2, 4),
+            Shape::new(3, 5),
+            Shape::new(4, 6),
+            Shape::new(6, 4),
+        ] {
+            let start = Node::new(Node::default_board(shape))
+                .unwrap()
+                .follow(&scramble)
+                .unwrap();
+            let problem = Problem::new(
+                start
+                    .get_board()
+                    .chunks(shape.width as usize)
+                    .map(|row| row.to_vec())
+                    .collect(),
+            );
+            assert!(problem.is_solvable());
+
+            let optimal = BfsSolver::new(SearchConfig::default())
+                .unwrap()
+                .solve(problem.clone())
+                .unwrap();
+            let solutions = [
+                BiBfsSolver::new(SearchConfig::default())
+                    .unwrap()
+                    .solve(problem.clone()),
+                AStarSolver::new(SearchConfig::informed(LinearConflict))
+                    .unwrap()
+                    .solve(problem.clone()),
+                IdaStarSolver::new(SearchConfig::informed(Manhattan))
+                    .unwrap()
+                    .solve(problem.clone()),
+            ];
+
+            assert!(optimal.result_len as usize <= scramble.len());
+            for solution in solutions {
+                let solution = solution.unwrap();
+                assert_eq!(solution.result_len, optimal.result_len, "{}", shape);
+                let end = start.follow(&solution.path).unwrap();
+                assert!(end.is_solved(&problem.goal()));
+            }
+        }
+    }
+
+    // Test if every solver reaches spiral, blank-first and arbitrary goals optimally
+    #[test]
+    fn test_custom_goals() {
+        let shape = Shape::square(3);
+        let custom = Goal::new(vec![vec![3, 1, 2], vec![6, 4, 5], vec![0, 7, 8]]).unwrap();
+        // Moves off the edge are skipped, so the scramble suits any blank position
+        let scramble = [
+            Dir::Down,
+            Dir::Right,
+            Dir::Right,
+            Dir::Up,
+            Dir::Left,
+            Dir::Down,
+            Dir::Left,
+            Dir::Up,
+            Dir::Right,
+            Dir::Down,
+        ];
+
+        for goal in [Goal::spiral(shape), Goal::blank_first(shape), custom] {
+            let mut start = Node::new(goal.table()).unwrap();
+            for dir in scramble {
+                start = start.get_node_for_move(dir).unwrap_or(start);
+            }
+            let table = start
+                .get_board()
+                .chunks(shape.width as usize)
+                .map(|row| row.to_vec())
+                .collect();
+            let problem = Problem::with_goal(table, goal.clone());
+            assert!(problem.is_solvable());
+
+            let groups = PatternDatabase::default_groups(shape).unwrap();
+            let pdb = PatternDatabase::build(&goal, &groups).unwrap();
+            let optimal = BfsSolver::new(SearchConfig::default())
+                .unwrap()
+                .solve(problem.clone())
+                .unwrap();
+            let solutions = [
+                BiBfsSolver::new(SearchConfig::default())
+                    .unwrap()
+                    .solve(problem.clone()),
+                AStarSolver::new(SearchConfig::informed(Hamming))
+                    .unwrap()
+                    .solve(problem.clone()),
+                AStarSolver::new(SearchConfig::informed(pdb))
+                    .unwrap()
+                    .solve(problem.clone()),
+                IdaStarSolver::new(SearchConfig::informed(WalkingDistance::default()))
+                    .unwrap()
+                    .solve(problem.clone()),
+                IdaStarSolver::new(SearchConfig::informed(LinearConflict))
+                    .unwrap()
+                    .solve(problem.clone()),
+            ];
+
+            assert!(start.follow(&optimal.path).unwrap().is_solved(&goal));
+            for solution in solutions {
+                let solution = solution.unwrap();
+                assert_eq!(solution.result_len, optimal.result_len);
+                assert!(start.follow(&solution.path).unwrap().is_solved(&goal));
+            }
+        }
+    }
+
+    // Test if a pattern database is refused for a goal it was not built for
+    #[test]
+    fn test_solver_reports_unsupported_goal() {
+        let shape = Shape::square(3);
+        let groups = PatternDatabase::default_groups(shape).unwrap();
+        let pdb = PatternDatabase::build(&Goal::standard(shape), &groups).unwrap();
+        let problem = Problem::with_goal(Goal::spiral(shape).table(), Goal::spiral(shape));
+
+        let mut solver = AStarSolver::new(SearchConfig::informed(pdb)).unwrap();
+        assert!(matches!(
+            solver.solve(problem),
+            Err(SolveError::UnsupportedConfig(ConfigError::UnsupportedGoal))
+        ));
+
+        let mismatched =
+            Problem::with_goal(Goal::spiral(shape).table(), Goal::spiral(Shape::new(2, 3)));
+        let mut solver = BfsSolver::new(SearchConfig::default()).unwrap();
+        assert!(matches!(
+            solver.solve(mismatched),
+            Err(SolveError::InvalidBoard(_))
+        ));
+    }
+
+    // Test if a 20x20 board, beyond the range of 8-bit tiles, is represented,
+    // evaluated and solved by the suboptimal searches
+    #[test]
+    fn test_large_board() {
+        let shape = Shape::square(20);
+        let goal = Goal::standard(shape);
+        assert_eq!(goal.tiles()[398], 399);
+
+        let mut start = Node::new(Node::default_board(shape)).unwrap();
+        let mut seed: u32 = 2024;
+        for _ in 0..100 {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let dir = Dir::values()[(seed >> 16) as usize % 4];
+            start = start.get_node_for_move(dir).unwrap_or(start);
+        }
+        let table: Vec<Vec<_>> = start
+            .get_board()
+            .chunks(shape.width as usize)
+            .map(|row| row.to_vec())
+            .collect();
+        let problem = Problem::new(table);
+        assert!(problem.is_solvable());
+        assert!(Manhattan.estimate(&start.get_board(), &goal) > 0);
+
+        let solutions = [
+            AStarSolver::greedy(SearchConfig::informed(LinearConflict))
+                .unwrap()
+                .solve(problem.clone()),
+            AStarSolver::weighted(SearchConfig::informed(Manhattan), 3.0)
+                .unwrap()
+                .solve(problem.clone()),
+        ];
+
+        for solution in solutions {
+            let solution = solution.unwrap();
+            assert!(start.follow(&solution.path).unwrap().is_solved(&goal));
+        }
+    }
 }
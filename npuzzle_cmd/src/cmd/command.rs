@@ -1,5 +1,10 @@
-use clap::{Parser, Subcommand, ValueEnum};
-use npuzzle_lib::core::{Dir, Order};
+use std::{sync::Arc, time::Duration};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use npuzzle_lib::{
+    core::{Dir, Goal, SearchLimits, Shape, input::Format},
+    heuristics::{Hamming, Heuristic, LinearConflict, Manhattan, WalkingDistance},
+};
 
 #[derive(Parser)]
 #[command(
@@ -20,6 +25,15 @@ pub enum Strategy {
         input_file: String,
         solution_file: String,
         stats_file: String,
+        #[command(flatten)]
+        limits: LimitArgs,
+        #[command(flatten)]
+        goal: GoalArgs,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Solve every puzzle of the input file, writing a row per puzzle
+        #[arg(long)]
+        batch: bool,
     },
     Dfs {
         #[arg(value_enum)]
@@ -27,14 +41,176 @@ pub enum Strategy {
         input_file: String,
         solution_file: String,
         stats_file: String,
+        #[command(flatten)]
+        limits: LimitArgs,
+        #[command(flatten)]
+        goal: GoalArgs,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Solve every puzzle of the input file, writing a row per puzzle
+        #[arg(long)]
+        batch: bool,
     },
     Astr {
-        #[arg(value_enum)]
+        #[arg(value_enum, requires_if("pdb", "pdb"))]
         heuristic: HeuristicType,
         input_file: String,
         solution_file: String,
         stats_file: String,
+        /// Weighted A*: expand nodes by g + w*h, solution is at most w times optimal
+        #[arg(long, value_name = "W", conflicts_with = "greedy")]
+        weight: Option<f64>,
+        /// Greedy best-first search: expand nodes by h alone
+        #[arg(long)]
+        greedy: bool,
+        /// Pattern database file built with `build-pdb`, for the `pdb` heuristic
+        #[arg(long, value_name = "FILE")]
+        pdb: Option<String>,
+        #[command(flatten)]
+        limits: LimitArgs,
+        #[command(flatten)]
+        goal: GoalArgs,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Solve every puzzle of the input file, writing a row per puzzle
+        #[arg(long)]
+        batch: bool,
+    },
+    /// Build an additive pattern database for A* and save it to a file
+    BuildPdb {
+        /// Board dimension (3, 4 or 5)
+        size: u8,
+        output_file: String,
+        #[command(flatten)]
+        goal: GoalArgs,
+    },
+    /// Generate random solvable boards and write them in the input file format
+    Generate {
+        rows: u8,
+        cols: u8,
+        /// File to write, or directory to fill when generating more than one board
+        output: String,
+        /// Number of moves of an optimal solution, any solvable board unless given
+        #[arg(long, value_name = "MOVES")]
+        distance: Option<u16>,
+        /// Number of boards to generate
+        #[arg(long, value_name = "N", default_value_t = 1)]
+        count: usize,
+        /// Seed of the random generator, taken from the clock unless given
+        #[arg(long)]
+        seed: Option<u64>,
+        #[command(flatten)]
+        goal: GoalArgs,
     },
+    /// Check that a solution file solves the puzzle of an input file
+    Verify {
+        input_file: String,
+        solution_file: String,
+        /// Length of an optimal solution, to also check that the solution is optimal
+        #[arg(long, value_name = "MOVES")]
+        optimal: Option<usize>,
+        #[command(flatten)]
+        goal: GoalArgs,
+        #[command(flatten)]
+        input: InputArgs,
+    },
+}
+
+// State the puzzle is solved towards, the standard goal unless given
+#[derive(Args, Clone, Debug)]
+pub struct GoalArgs {
+    /// Built-in goal layout
+    #[arg(long, value_enum, default_value_t = GoalType::Standard)]
+    pub goal: GoalType,
+    /// File holding the goal board, in the format of the input file
+    #[arg(long, value_name = "FILE", conflicts_with = "goal")]
+    pub goal_file: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum GoalType {
+    /// Tiles in order, blank in the bottom right corner
+    Standard,
+    /// Tiles along a clockwise spiral, blank in its centre
+    Spiral,
+    /// Blank in the top left corner, tiles in order after it
+    BlankFirst,
+}
+
+impl GoalType {
+    pub fn goal(self, shape: Shape) -> Goal {
+        match self {
+            GoalType::Standard => Goal::standard(shape),
+            GoalType::Spiral => Goal::spiral(shape),
+            GoalType::BlankFirst => Goal::blank_first(shape),
+        }
+    }
+}
+
+// Layout of the input file, told from its content unless given
+#[derive(Args, Clone, Debug)]
+pub struct InputArgs {
+    /// Format of the input file
+    #[arg(long, value_enum, default_value_t = InputFormat::Auto)]
+    pub format: InputFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum InputFormat {
+    /// Detected from the first line of the file
+    Auto,
+    /// Header line `rows cols` followed by a line of tiles per row
+    Grid,
+    /// Header and tiles on one line, `3 3 | 1 2 3 4 5 6 7 0 8`
+    Line,
+    /// Rows as JSON arrays, `[[1, 2, 3], [4, 5, 6], [7, 0, 8]]`
+    Json,
+}
+
+impl InputFormat {
+    pub fn format(self, content: &str) -> Format {
+        match self {
+            InputFormat::Auto => Format::detect(content),
+            InputFormat::Grid => Format::Grid,
+            InputFormat::Line => Format::Line,
+            InputFormat::Json => Format::Json,
+        }
+    }
+}
+
+// Bounds on a single search, unbounded unless given
+#[derive(Args, Clone, Debug)]
+pub struct LimitArgs {
+    /// Deepest level to search, DFS defaults to 20
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<u16>,
+    /// Number of nodes the search may expand
+    #[arg(long, value_name = "NODES")]
+    pub max_nodes: Option<usize>,
+    /// Time the search may take, in seconds
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+    /// Approximate memory the stored nodes may take, in megabytes
+    #[arg(long, value_name = "MB")]
+    pub max_memory: Option<usize>,
+}
+
+impl From<LimitArgs> for SearchLimits {
+    fn from(args: LimitArgs) -> Self {
+        SearchLimits {
+            max_depth: args.max_depth,
+            max_nodes: args.max_nodes,
+            timeout: args.timeout,
+            max_memory: args.max_memory.map(|mb| mb.saturating_mul(1024 * 1024)),
+        }
+    }
+}
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", arg))?;
+    Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -66,42 +242,45 @@ pub enum SearchOrder {
     Ldru,
 }
 
-impl From<HeuristicType> for Order {
+impl From<HeuristicType> for Arc<dyn Heuristic> {
     fn from(value: HeuristicType) -> Self {
         match value {
-            HeuristicType::Manh => Order::Manh,
-            HeuristicType::Hamm => Order::Hamm,
+            HeuristicType::Manh => Arc::new(Manhattan),
+            HeuristicType::Hamm => Arc::new(Hamming),
+            HeuristicType::Lcon => Arc::new(LinearConflict),
+            HeuristicType::Walk => Arc::new(WalkingDistance::default()),
+            HeuristicType::Pdb => unreachable!("Pattern databases are loaded from their file"),
         }
     }
 }
 
-impl From<SearchOrder> for Order {
+impl From<SearchOrder> for [Dir; 4] {
     fn from(search_order: SearchOrder) -> Self {
         match search_order {
-            SearchOrder::Rdul => Order::Perm([Dir::Right, Dir::Down, Dir::Up, Dir::Left]),
-            SearchOrder::Rdlu => Order::Perm([Dir::Right, Dir::Down, Dir::Left, Dir::Up]),
-            SearchOrder::Ruld => Order::Perm([Dir::Right, Dir::Up, Dir::Left, Dir::Down]),
-            SearchOrder::Rudl => Order::Perm([Dir::Right, Dir::Up, Dir::Down, Dir::Left]),
-            SearchOrder::Rlud => Order::Perm([Dir::Right, Dir::Left, Dir::Up, Dir::Down]),
-            SearchOrder::Rldu => Order::Perm([Dir::Right, Dir::Left, Dir::Down, Dir::Up]),
-            SearchOrder::Drul => Order::Perm([Dir::Down, Dir::Right, Dir::Up, Dir::Left]),
-            SearchOrder::Drlu => Order::Perm([Dir::Down, Dir::Right, Dir::Left, Dir::Up]),
-            SearchOrder::Dulr => Order::Perm([Dir::Down, Dir::Up, Dir::Left, Dir::Right]),
-            SearchOrder::Durl => Order::Perm([Dir::Down, Dir::Up, Dir::Right, Dir::Left]),
-            SearchOrder::Dlur => Order::Perm([Dir::Down, Dir::Left, Dir::Up, Dir::Right]),
-            SearchOrder::Dlru => Order::Perm([Dir::Down, Dir::Left, Dir::Right, Dir::Up]),
-            SearchOrder::Ulrd => Order::Perm([Dir::Up, Dir::Left, Dir::Right, Dir::Down]),
-            SearchOrder::Uldr => Order::Perm([Dir::Up, Dir::Left, Dir::Down, Dir::Right]),
-            SearchOrder::Urld => Order::Perm([Dir::Up, Dir::Right, Dir::Left, Dir::Down]),
-            SearchOrder::Urdl => Order::Perm([Dir::Up, Dir::Right, Dir::Down, Dir::Left]),
-            SearchOrder::Udlr => Order::Perm([Dir::Up, Dir::Down, Dir::Left, Dir::Right]),
-            SearchOrder::Udrl => Order::Perm([Dir::Up, Dir::Down, Dir::Right, Dir::Left]),
-            SearchOrder::Lrud => Order::Perm([Dir::Left, Dir::Right, Dir::Up, Dir::Down]),
-            SearchOrder::Lrdu => Order::Perm([Dir::Left, Dir::Right, Dir::Down, Dir::Up]),
-            SearchOrder::Lurd => Order::Perm([Dir::Left, Dir::Up, Dir::Right, Dir::Down]),
-            SearchOrder::Ludr => Order::Perm([Dir::Left, Dir::Up, Dir::Down, Dir::Right]),
-            SearchOrder::Ldur => Order::Perm([Dir::Left, Dir::Down, Dir::Up, Dir::Right]),
-            SearchOrder::Ldru => Order::Perm([Dir::Left, Dir::Down, Dir::Right, Dir::Up]),
+            SearchOrder::Rdul => [Dir::Right, Dir::Down, Dir::Up, Dir::Left],
+            SearchOrder::Rdlu => [Dir::Right, Dir::Down, Dir::Left, Dir::Up],
+            SearchOrder::Ruld => [Dir::Right, Dir::Up, Dir::Left, Dir::Down],
+            SearchOrder::Rudl => [Dir::Right, Dir::Up, Dir::Down, Dir::Left],
+            SearchOrder::Rlud => [Dir::Right, Dir::Left, Dir::Up, Dir::Down],
+            SearchOrder::Rldu => [Dir::Right, Dir::Left, Dir::Down, Dir::Up],
+            SearchOrder::Drul => [Dir::Down, Dir::Right, Dir::Up, Dir::Left],
+            SearchOrder::Drlu => [Dir::Down, Dir::Right, Dir::Left, Dir::Up],
+            SearchOrder::Dulr => [Dir::Down, Dir::Up, Dir::Left, Dir::Right],
+            SearchOrder::Durl => [Dir::Down, Dir::Up, Dir::Right, Dir::Left],
+            SearchOrder::Dlur => [Dir::Down, Dir::Left, Dir::Up, Dir::Right],
+            SearchOrder::Dlru => [Dir::Down, Dir::Left, Dir::Right, Dir::Up],
+            SearchOrder::Ulrd => [Dir::Up, Dir::Left, Dir::Right, Dir::Down],
+            SearchOrder::Uldr => [Dir::Up, Dir::Left, Dir::Down, Dir::Right],
+            SearchOrder::Urld => [Dir::Up, Dir::Right, Dir::Left, Dir::Down],
+            SearchOrder::Urdl => [Dir::Up, Dir::Right, Dir::Down, Dir::Left],
+            SearchOrder::Udlr => [Dir::Up, Dir::Down, Dir::Left, Dir::Right],
+            SearchOrder::Udrl => [Dir::Up, Dir::Down, Dir::Right, Dir::Left],
+            SearchOrder::Lrud => [Dir::Left, Dir::Right, Dir::Up, Dir::Down],
+            SearchOrder::Lrdu => [Dir::Left, Dir::Right, Dir::Down, Dir::Up],
+            SearchOrder::Lurd => [Dir::Left, Dir::Up, Dir::Right, Dir::Down],
+            SearchOrder::Ludr => [Dir::Left, Dir::Up, Dir::Down, Dir::Right],
+            SearchOrder::Ldur => [Dir::Left, Dir::Down, Dir::Up, Dir::Right],
+            SearchOrder::Ldru => [Dir::Left, Dir::Down, Dir::Right, Dir::Up],
         }
     }
 }
@@ -110,4 +289,10 @@ impl From<SearchOrder> for Order {
 pub enum HeuristicType {
     Manh,
     Hamm,
+    /// Manhattan distance with linear conflicts
+    Lcon,
+    /// Walking distance
+    Walk,
+    /// Additive pattern database read from the file given with `--pdb`
+    Pdb,
 }
@@ -1,17 +1,29 @@
-#![feature(generic_arg_infer)]
-
-use std::fs;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use clap::Parser;
-use cmd::command::{Cli, Strategy};
-use npuzzle_lib::core::{
-    Problem, Solution,
-    order::Order,
-    solver::{AStarSolver, BfsSolver, DfsSolver},
+use cmd::command::{Cli, GoalArgs, HeuristicType, InputArgs, Strategy};
+use npuzzle_lib::{
+    core::{
+        Generator, Goal, Moves, Problem, SearchConfig, Shape, Solution, SolveError,
+        input::{parse_board_as, parse_boards},
+        solver::{AStarSolver, BfsSolver, DfsSolver},
+        verify,
+    },
+    heuristics::{Heuristic, PatternDatabase},
 };
 
 mod cmd;
 
+// Depth limit of DFS when none is given, without one it may return a path of
+// thousands of moves
+const DFS_MAX_DEPTH: u16 = 20;
+
 fn main() {
     let cli = Cli::parse();
 
@@ -21,49 +33,235 @@ fn main() {
             input_file,
             solution_file,
             stats_file,
+            limits,
+            goal,
+            input,
+            batch,
         } => {
-            let problem = load_problem(input_file);
-            let mut solver = BfsSolver::new(Order::from((*order).clone()));
-            let solution = solver.solve(problem.clone());
-            if let Some(solved) = solution {
-                write_solution_file(&solved, solution_file);
-                write_stats_file(&solved, stats_file);
-            } else {
-                write_solution_file_err(solution_file);
-                write_solution_file_err(stats_file);
-            }
+            let problems = load_problems(input_file, input, goal, *batch);
+            let config = SearchConfig::builder()
+                .order((*order).clone().into())
+                .limits(limits.clone().into())
+                .build()
+                .unwrap_or_else(|err| fail(err));
+            let mut solver = BfsSolver::new(config).unwrap_or_else(|err| fail(err));
+            write_results(problems, *batch, solution_file, stats_file, |problem| {
+                solver.solve(problem)
+            });
         }
         Strategy::Dfs {
             order,
             input_file,
             solution_file,
             stats_file,
+            limits,
+            goal,
+            input,
+            batch,
         } => {
-            let problem = load_problem(input_file);
-            let mut solver = DfsSolver::new(Order::from((*order).clone()));
-            let solution = solver.solve(problem.clone());
-            if let Some(solved) = solution {
-                write_solution_file(&solved, solution_file);
-                write_stats_file(&solved, stats_file);
-            }
+            let problems = load_problems(input_file, input, goal, *batch);
+            let config = SearchConfig::builder()
+                .order((*order).clone().into())
+                .limits(limits.clone().into())
+                .max_depth(limits.max_depth.unwrap_or(DFS_MAX_DEPTH))
+                .build()
+                .unwrap_or_else(|err| fail(err));
+            let mut solver = DfsSolver::new(config).unwrap_or_else(|err| fail(err));
+            write_results(problems, *batch, solution_file, stats_file, |problem| {
+                solver.solve(problem)
+            });
         }
         Strategy::Astr {
             heuristic,
             input_file,
             solution_file,
             stats_file,
+            weight,
+            greedy,
+            pdb,
+            limits,
+            goal,
+            input,
+            batch,
+        } => {
+            let problems = load_problems(input_file, input, goal, *batch);
+            let heuristic: Arc<dyn Heuristic> = match (heuristic, pdb) {
+                (HeuristicType::Pdb, Some(path)) => Arc::new(load_pdb(path, &problems[0])),
+                (_, Some(_)) => fail("A pattern database is only used by the pdb heuristic"),
+                (heuristic, None) => heuristic.clone().into(),
+            };
+            let config = SearchConfig::builder()
+                .shared_heuristic(heuristic)
+                .limits(limits.clone().into())
+                .build()
+                .unwrap_or_else(|err| fail(err));
+            let mut solver = match weight {
+                Some(w) => AStarSolver::weighted(config, *w),
+                None if *greedy => AStarSolver::greedy(config),
+                None => AStarSolver::new(config),
+            }
+            .unwrap_or_else(|err| fail(err));
+            write_results(problems, *batch, solution_file, stats_file, |problem| {
+                solver.solve(problem)
+            });
+        }
+        Strategy::BuildPdb {
+            size,
+            output_file,
+            goal,
         } => {
-            let problem = load_problem(input_file);
-            let mut solver = AStarSolver::new(Order::from((*heuristic).clone()));
-            let solution = solver.solve(problem.clone());
-            if let Some(solved) = solution {
-                write_solution_file(&solved, solution_file);
-                write_stats_file(&solved, stats_file);
+            let shape = Shape::square(*size);
+            let Some(groups) = PatternDatabase::default_groups(shape) else {
+                fail(format!("No default pattern partition for {} boards", shape));
+            };
+            let goal = load_goal(goal, shape);
+            if goal.shape() != shape {
+                fail(format!("Goal file holds a {} board", goal.shape()));
             }
+            let pdb = PatternDatabase::build(&goal, &groups).unwrap_or_else(|err| fail(err));
+            pdb.save(output_file)
+                .unwrap_or_else(|err| fail(format!("{}: {}", output_file, err)));
+        }
+        Strategy::Generate {
+            rows,
+            cols,
+            output,
+            distance,
+            count,
+            seed,
+            goal,
+        } => {
+            let shape = Shape::new(*rows, *cols);
+            if shape.cells() < 2 {
+                fail(format!("Cannot generate {} boards", shape));
+            }
+            let goal = load_goal(goal, shape);
+            if goal.shape() != shape {
+                fail(format!("Goal file holds a {} board", goal.shape()));
+            }
+
+            let seed = seed.unwrap_or_else(|| {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+                now.as_nanos() as u64
+            });
+            println!("Seed: {}", seed);
+
+            let mut generator = Generator::new(seed);
+            if *count > 1 {
+                fs::create_dir_all(output).expect("Failed to create output directory");
+            }
+
+            for index in 1..=*count {
+                let problem = match distance {
+                    Some(moves) => generator.at_distance(&goal, *moves).unwrap_or_else(|err| {
+                        fail(format!(
+                            "Cannot generate a {} board {} moves from the goal: {}",
+                            shape, moves, err
+                        ))
+                    }),
+                    None => generator.random(&goal),
+                };
+
+                // Named as in the reference puzzle sets, `4x4_07_00042.txt`
+                let path = match (count, distance) {
+                    (1, _) => PathBuf::from(output),
+                    (_, Some(moves)) => Path::new(output)
+                        .join(format!("{}x{}_{:02}_{:05}.txt", rows, cols, moves, index)),
+                    (_, None) => {
+                        Path::new(output).join(format!("{}x{}_{:05}.txt", rows, cols, index))
+                    }
+                };
+                write_problem_file(&problem, &path);
+            }
+        }
+        Strategy::Verify {
+            input_file,
+            solution_file,
+            optimal,
+            goal,
+            input,
+        } => {
+            let problem = load_problems(input_file, input, goal, false).remove(0);
+            let path = read_solution_file(solution_file);
+
+            verify(&problem, &path, *optimal).unwrap_or_else(|err| fail(err));
+            println!("Solution of {} moves is valid", path.len());
         }
     }
 }
 
+fn fail(err: impl fmt::Display) -> ! {
+    eprintln!("{}", err);
+    process::exit(1);
+}
+
+fn load_pdb(path: &str, problem: &Problem) -> PatternDatabase {
+    let pdb = PatternDatabase::load(path).unwrap_or_else(|err| fail(format!("{}: {}", path, err)));
+    if pdb.shape() != problem.shape() {
+        fail(format!(
+            "Pattern database is built for {} boards",
+            pdb.shape()
+        ));
+    }
+    if *pdb.goal() != problem.goal() {
+        fail("Pattern database is built for a different goal");
+    }
+    pdb
+}
+
+fn write_result(result: Result<Solution, SolveError>, solution_file: &str, stats_file: &str) {
+    match result {
+        Ok(solved) => {
+            write_solution_file(&solved, solution_file);
+            write_stats_file(&solved, stats_file);
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            write_solution_file_err(solution_file);
+            write_solution_file_err(stats_file);
+        }
+    }
+}
+
+// Solves every problem, writing the files of a single solution, or a row per
+// problem in batch mode
+fn write_results(
+    problems: Vec<Problem>,
+    batch: bool,
+    solution_file: &str,
+    stats_file: &str,
+    mut solve: impl FnMut(Problem) -> Result<Solution, SolveError>,
+) {
+    if !batch {
+        for problem in problems {
+            write_result(solve(problem), solution_file, stats_file);
+        }
+        return;
+    }
+
+    let (mut solutions, mut stats) = (String::new(), String::new());
+    for (index, problem) in problems.into_iter().enumerate() {
+        match solve(problem) {
+            Ok(solved) => {
+                let moves = Moves::new(solved.path.clone());
+                let row = format!("{} {}", solved.result_len, moves);
+                solutions.push_str(row.trim_end());
+                stats.push_str(&format_stats(&solved, " "));
+            }
+            Err(err) => {
+                eprintln!("Puzzle {}: {}", index + 1, err);
+                solutions.push_str("-1");
+                stats.push_str("-1");
+            }
+        }
+        solutions.push('\n');
+        stats.push('\n');
+    }
+
+    fs::write(solution_file, solutions).expect("Failed to write solution file");
+    fs::write(stats_file, stats).expect("Failed to write stats file");
+}
+
 fn write_solution_file(solution: &Solution, path: &str) {
     let mut moves = solution
         .path
@@ -77,6 +275,46 @@ fn write_solution_file(solution: &Solution, path: &str) {
     fs::write(path, moves).expect("Failed to write solution file");
 }
 
+fn write_problem_file(problem: &Problem, path: &Path) {
+    let shape = problem.shape();
+    let mut content = format!("{} {}\n", shape.height, shape.width);
+    for row in problem.get() {
+        let row: Vec<String> = row.iter().map(|tile| tile.to_string()).collect();
+        content.push_str(&row.join(" "));
+        content.push('\n');
+    }
+
+    fs::write(path, content).expect("Failed to write puzzle file");
+}
+
+// Reads the moves of a file written by `write_solution_file`, checking them
+// against the length on its first line
+fn read_solution_file(path: &str) -> Moves {
+    let content = fs::read_to_string(path).expect("Failed to read solution file");
+    let mut lines = content.lines();
+
+    let length = lines.next().unwrap_or_default().trim();
+    let length: usize = match length.parse::<i64>() {
+        Ok(-1) => fail("Solution file reports that no solution was found"),
+        Ok(length) if length >= 0 => length as usize,
+        _ => fail(format!("Invalid solution length '{}'", length)),
+    };
+
+    let moves: Moves = lines
+        .collect::<String>()
+        .parse()
+        .unwrap_or_else(|err| fail(err));
+    if moves.len() != length {
+        fail(format!(
+            "Solution file declares {} moves but holds {}",
+            length,
+            moves.len()
+        ));
+    }
+
+    moves
+}
+
 fn write_solution_file_err(path: &str) {
     let moves = format!("{}", -1);
 
@@ -84,25 +322,50 @@ fn write_solution_file_err(path: &str) {
 }
 
 fn write_stats_file(solution: &Solution, path: &str) {
-    let stats = format!(
-        "{}\n{}\n{}\n{}\n{:.3}",
-        solution.result_len,
-        solution.visited_count,
-        solution.processed_count,
-        solution.reached_depth,
-        solution.duration
-    );
-
-    fs::write(path, stats).expect("Failed to write stats file");
+    fs::write(path, format_stats(solution, "\n")).expect("Failed to write stats file");
 }
 
-fn load_problem(input: &str) -> Problem {
+fn format_stats(solution: &Solution, separator: &str) -> String {
+    [
+        solution.result_len.to_string(),
+        solution.visited_count.to_string(),
+        solution.processed_count.to_string(),
+        solution.reached_depth.to_string(),
+        format!("{:.3}", solution.duration),
+    ]
+    .join(separator)
+}
+
+// Problems of the input file, exactly one unless in batch mode
+fn load_problems(input: &str, args: &InputArgs, goal: &GoalArgs, batch: bool) -> Vec<Problem> {
     let content = fs::read_to_string(input).expect("Failed to read input file");
-    let mut array: Vec<Vec<u8>> = vec![];
-    for x in content.lines().skip(1) {
-        let v: Vec<u8> = x.split_whitespace().map(|x| x.parse().unwrap()).collect();
-        array.push(v);
+    let format = args.format.format(&content);
+    let tables = if batch {
+        parse_boards(&content, format)
+    } else {
+        parse_board_as(&content, format).map(|table| vec![table])
     }
+    .unwrap_or_else(|err| fail(format!("{}: {}", input, err)));
+
+    let goal_file = goal.goal_file.as_deref().map(read_goal_file);
+    tables
+        .into_iter()
+        .map(|table| {
+            let shape = Problem::new(table.clone()).shape();
+            let goal = goal_file.clone().unwrap_or_else(|| goal.goal.goal(shape));
+            Problem::with_goal(table, goal)
+        })
+        .collect()
+}
+
+fn load_goal(args: &GoalArgs, shape: Shape) -> Goal {
+    match &args.goal_file {
+        Some(path) => read_goal_file(path),
+        None => args.goal.goal(shape),
+    }
+}
 
-    Problem::new(array)
+fn read_goal_file(path: &str) -> Goal {
+    let content = fs::read_to_string(path).expect("Failed to read goal file");
+    Goal::from_string(&content).unwrap_or_else(|err| fail(format!("{}: {}", path, err)))
 }
@@ -0,0 +1,65 @@
+use std::time::Instant;
+
+use npuzzle_lib::core::{Board, Dir, Goal, node::Node};
+
+// Depth of the walked search tree, 4x4 boards branch about 2.13 times per level
+const DEPTH: u16 = 22;
+
+/// Compares how many nodes per second a depth-first walk visits when every
+/// child is a new `Node` (as depth-first searches did before `Board`) and
+/// when a single `Board` is changed in place.
+pub fn run() {
+    let start = Node::new(vec![
+        vec![5, 1, 2, 3],
+        vec![0, 6, 7, 4],
+        vec![9, 10, 11, 8],
+        vec![13, 14, 15, 12],
+    ])
+    .unwrap();
+
+    let timer = Instant::now();
+    let nodes = walk_nodes(&start, DEPTH);
+    let before = nodes as f64 / timer.elapsed().as_secs_f64();
+    println!("Node children: {} nodes, {:.0} nodes/s", nodes, before);
+
+    let timer = Instant::now();
+    let nodes = walk_board(
+        &mut Board::new(&start, &Goal::standard(start.shape())),
+        None,
+        DEPTH,
+    );
+    let after = nodes as f64 / timer.elapsed().as_secs_f64();
+    println!("Board apply/undo: {} nodes, {:.0} nodes/s", nodes, after);
+
+    println!("Speed-up: {:.1}x", after / before);
+}
+
+fn walk_nodes(node: &Node, depth: u16) -> usize {
+    if depth == 0 {
+        return 1;
+    }
+
+    1 + node
+        .get_child_nodes(Dir::values())
+        .iter()
+        .filter(|child| node.next_move() != child.next_move().map(|dir| dir.reverse()))
+        .map(|child| walk_nodes(child, depth - 1))
+        .sum::<usize>()
+}
+
+fn walk_board(board: &mut Board, last: Option<Dir>, depth: u16) -> usize {
+    if depth == 0 {
+        return 1;
+    }
+
+    let mut count = 1;
+    for dir in Dir::values() {
+        if last == Some(dir.reverse()) || !board.apply(dir) {
+            continue;
+        }
+        count += walk_board(board, Some(dir), depth - 1);
+        board.undo(dir);
+    }
+
+    count
+}
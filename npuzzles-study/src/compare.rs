@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use npuzzle_lib::{
+    core::{Generator, Goal, SearchConfig, Shape, solver::AStarSolver},
+    heuristics::{Heuristic, LinearConflict, Manhattan, WalkingDistance},
+};
+
+// Optimal solution lengths of the compared boards, for every board size
+const DISTANCES: [(u8, &[u16]); 2] = [(3, &[10, 20, 30]), (4, &[10, 15, 20])];
+
+// Boards generated for every size and distance
+const BOARDS: usize = 10;
+
+const SEED: u64 = 2024;
+
+/// Compares A* with Manhattan distance, linear conflict and walking distance
+/// per board size, averaged over generated boards of the same optimal
+/// solution length.
+pub fn run() {
+    let heuristics: [Arc<dyn Heuristic>; 3] = [
+        Arc::new(Manhattan),
+        Arc::new(LinearConflict),
+        Arc::new(WalkingDistance::default()),
+    ];
+
+    for (size, distances) in DISTANCES {
+        let goal = Goal::standard(Shape::square(size));
+        let mut generator = Generator::new(SEED);
+
+        for &distance in distances {
+            let boards: Vec<_> = (0..BOARDS)
+                .map(|_| generator.at_distance(&goal, distance).unwrap())
+                .collect();
+
+            for heuristic in &heuristics {
+                let config = SearchConfig::builder()
+                    .shared_heuristic(heuristic.clone())
+                    .build()
+                    .unwrap();
+                let mut solver = AStarSolver::new(config).unwrap();
+
+                let (mut processed, mut duration) = (0, 0.0);
+                for board in &boards {
+                    let solution = solver.solve(board.clone()).unwrap();
+                    processed += solution.processed_count;
+                    duration += solution.duration;
+                }
+
+                println!(
+                    "{} {:>2} moves ASTAR-{}: {} nodes, {:.3} ms",
+                    goal.shape(),
+                    distance,
+                    heuristic.name().to_uppercase(),
+                    processed / BOARDS,
+                    duration / BOARDS as f64
+                );
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use npuzzle_lib::core::{Dir, Order};
+use npuzzle_lib::core::Dir;
 
 #[derive(Clone)]
 pub enum So {
@@ -27,17 +27,17 @@ impl So {
     }
 }
 
-impl From<So> for Order {
+impl From<So> for [Dir; 4] {
     fn from(search_order: So) -> Self {
         match search_order {
-            So::Rdul => Order::Perm([Dir::Right, Dir::Down, Dir::Up, Dir::Left]),
-            So::Rdlu => Order::Perm([Dir::Right, Dir::Down, Dir::Left, Dir::Up]),
-            So::Drul => Order::Perm([Dir::Down, Dir::Right, Dir::Up, Dir::Left]),
-            So::Drlu => Order::Perm([Dir::Down, Dir::Right, Dir::Left, Dir::Up]),
-            So::Ulrd => Order::Perm([Dir::Up, Dir::Left, Dir::Right, Dir::Down]),
-            So::Uldr => Order::Perm([Dir::Up, Dir::Left, Dir::Down, Dir::Right]),
-            So::Lurd => Order::Perm([Dir::Left, Dir::Up, Dir::Right, Dir::Down]),
-            So::Ludr => Order::Perm([Dir::Left, Dir::Up, Dir::Down, Dir::Right]),
+            So::Rdul => [Dir::Right, Dir::Down, Dir::Up, Dir::Left],
+            So::Rdlu => [Dir::Right, Dir::Down, Dir::Left, Dir::Up],
+            So::Drul => [Dir::Down, Dir::Right, Dir::Up, Dir::Left],
+            So::Drlu => [Dir::Down, Dir::Right, Dir::Left, Dir::Up],
+            So::Ulrd => [Dir::Up, Dir::Left, Dir::Right, Dir::Down],
+            So::Uldr => [Dir::Up, Dir::Left, Dir::Down, Dir::Right],
+            So::Lurd => [Dir::Left, Dir::Up, Dir::Right, Dir::Down],
+            So::Ludr => [Dir::Left, Dir::Up, Dir::Down, Dir::Right],
         }
     }
 }
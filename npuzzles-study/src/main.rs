@@ -1,18 +1,30 @@
-use std::{
-    collections::HashMap,
-    env, fs,
-    io::{self, Read},
-    path::Path,
-};
+use std::{collections::BTreeMap, env, ops::RangeInclusive};
 
+pub mod bench;
+pub mod compare;
 pub mod helper;
 
 use helper::So;
-use npuzzle_lib::core::{
-    Order, Problem,
-    solver::{AStarSolver, BfsSolver, DfsSolver, SolverEnum},
+use npuzzle_lib::{
+    core::{
+        Generator, Goal, Problem, SearchConfig, Shape,
+        solver::{AStarSolver, BfsSolver, DfsSolver, IddfsSolver, SolverEnum},
+    },
+    heuristics::{Hamming, LinearConflict, Manhattan, WalkingDistance},
 };
 
+// Depth limit of the depth-first runs, which would otherwise wander far from
+// the shallow study boards
+const DFS_MAX_DEPTH: u16 = 20;
+
+// Optimal solution lengths of the compared 4x4 boards
+const DEPTHS: RangeInclusive<u16> = 1..=7;
+
+// Boards generated for every depth
+const BOARDS: usize = 50;
+
+const SEED: u64 = 2024;
+
 #[derive(Default, Debug)]
 struct Temp {
     result_len: usize,
@@ -25,164 +37,185 @@ struct Temp {
 fn main() {
     // Collect command-line arguments
     let args: Vec<String> = env::args().collect();
+    let boards = generate_boards();
 
     // Print out the arguments
     for arg in args.iter() {
         if arg == "norm" {
-            let mut solver = SolverEnum::AStar(AStarSolver::new(Order::Manh));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("ASTAR-MANH:\n{:?}", x.unwrap());
-
-            let mut solver = SolverEnum::AStar(AStarSolver::new(Order::Hamm));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("ASTAR-Hamm:\n{:?}", x.unwrap());
-
-            let order = Order::from(So::Ludr);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Lurd);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Rdul);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Rdlu);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Ulrd);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Uldr);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Drul);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
-
-            let order = Order::from(So::Drlu);
-            let mut solver = SolverEnum::Bfs(BfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let mut solver =
+                SolverEnum::AStar(AStarSolver::new(SearchConfig::informed(Manhattan)).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("ASTAR-MANH:\n{:?}", x);
+
+            let mut solver =
+                SolverEnum::AStar(AStarSolver::new(SearchConfig::informed(Hamming)).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("ASTAR-HAMM:\n{:?}", x);
+
+            let mut solver = SolverEnum::AStar(
+                AStarSolver::new(SearchConfig::informed(LinearConflict)).unwrap(),
+            );
+            let x = solve_boards(&boards, &mut solver);
+            println!("ASTAR-LCON:\n{:?}", x);
+
+            let mut solver = SolverEnum::AStar(
+                AStarSolver::new(SearchConfig::informed(WalkingDistance::default())).unwrap(),
+            );
+            let x = solve_boards(&boards, &mut solver);
+            println!("ASTAR-WALK:\n{:?}", x);
+
+            let config = so_config(So::Ludr);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Lurd);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Rdul);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Rdlu);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Ulrd);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Uldr);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Drul);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+
+            let config = so_config(So::Drlu);
+            let mut solver = SolverEnum::Bfs(BfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
+        } else if arg == "bench" {
+            bench::run();
+        } else if arg == "heuristics" {
+            compare::run();
+        } else if arg == "iddfs" {
+            for so in So::values() {
+                let config = so_config(so.clone());
+                let mut solver = SolverEnum::Iddfs(IddfsSolver::new(config.clone()).unwrap());
+                let x = solve_boards(&boards, &mut solver);
+                println!("IDDFS-{}:\n{:?}", config, x);
+            }
         } else if arg == "ludr" {
-            let order = Order::from(So::Ludr);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Ludr);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "lurd" {
-            let order = Order::from(So::Lurd);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Lurd);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "rdul" {
-            let order = Order::from(So::Rdul);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Rdul);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "rdlu" {
-            let order = Order::from(So::Rdlu);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Rdlu);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "ulrd" {
-            let order = Order::from(So::Ulrd);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Ulrd);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "uldr" {
-            let order = Order::from(So::Uldr);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Uldr);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "drul" {
-            let order = Order::from(So::Drul);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Drul);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         } else if arg == "drlu" {
-            let order = Order::from(So::Drlu);
-            let mut solver = SolverEnum::Dfs(DfsSolver::new(order.clone()));
-            let x = parse_directory("puzzles", &mut solver);
-            println!("BFS-{}:\n{:?}", order, x.unwrap());
+            let config = dfs_config(So::Drlu);
+            let mut solver = SolverEnum::Dfs(DfsSolver::new(config.clone()).unwrap());
+            let x = solve_boards(&boards, &mut solver);
+            println!("BFS-{}:\n{:?}", config, x);
         }
     }
 }
 
-fn load_problem(input: &Path) -> io::Result<Problem> {
-    let mut file = fs::File::open(input)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    let content = fs::read_to_string(input).expect("Failed to read input file");
-    let mut array: Vec<Vec<u8>> = vec![];
-    for x in content.lines().skip(1) {
-        let v: Vec<u8> = x.split_whitespace().map(|x| x.parse().unwrap()).collect();
-        array.push(v);
-    }
-
-    Ok(Problem::new(array))
+fn so_config(so: So) -> SearchConfig {
+    SearchConfig::builder().order(so.into()).build().unwrap()
 }
 
-fn parse_directory(dir_path: &str, solver: &mut SolverEnum) -> io::Result<HashMap<u8, Temp>> {
-    let mut temp: HashMap<u8, Temp> = HashMap::new();
-
-    temp.insert(1, Temp::default());
-    temp.insert(2, Temp::default());
-    temp.insert(3, Temp::default());
-    temp.insert(4, Temp::default());
-    temp.insert(5, Temp::default());
-    temp.insert(6, Temp::default());
-    temp.insert(7, Temp::default());
-
-    let mut counter: [u16; 7] = [0, 0, 0, 0, 0, 0, 0];
+fn dfs_config(so: So) -> SearchConfig {
+    SearchConfig::builder()
+        .order(so.into())
+        .max_depth(DFS_MAX_DEPTH)
+        .build()
+        .unwrap()
+}
 
-    for entry in fs::read_dir(dir_path)? {
-        let path = entry?.path();
+fn generate_boards() -> Vec<(u16, Vec<Problem>)> {
+    let goal = Goal::standard(Shape::square(4));
+    let mut generator = Generator::new(SEED);
+
+    DEPTHS
+        .map(|depth| {
+            let problems = (0..BOARDS)
+                .map(|_| generator.at_distance(&goal, depth).unwrap())
+                .collect();
+            (depth, problems)
+        })
+        .collect()
+}
 
-        if path.is_file() {
-            let p = load_problem(&path).unwrap();
-            let s = AStarSolver::new(Order::Manh).solve(p.clone()).unwrap();
-            let shuffle_len = s.reached_depth as u8;
+fn solve_boards(boards: &[(u16, Vec<Problem>)], solver: &mut SolverEnum) -> BTreeMap<u16, Temp> {
+    let mut temp: BTreeMap<u16, Temp> = BTreeMap::new();
 
-            counter[(shuffle_len - 1) as usize] += 1;
+    for (depth, problems) in boards {
+        let o = temp.entry(*depth).or_default();
 
+        for p in problems {
+            let p = p.clone();
             let solution = match solver {
                 SolverEnum::Dfs(solver) => solver.solve(p),
                 SolverEnum::Bfs(solver) => solver.solve(p),
                 SolverEnum::AStar(solver) => solver.solve(p),
+                SolverEnum::IdaStar(solver) => solver.solve(p),
+                SolverEnum::Iddfs(solver) => solver.solve(p),
+                SolverEnum::BiBfs(solver) => solver.solve(p),
             }
             .unwrap();
 
-            if let Some(o) = temp.get_mut(&shuffle_len) {
-                o.result_len += solution.result_len as usize;
-                o.reached_depth += solution.reached_depth as usize;
-                o.visited_count += solution.visited_count;
-                o.processed_count += solution.processed_count;
-                o.duration += solution.duration;
-            }
+            o.result_len += solution.result_len as usize;
+            o.reached_depth += solution.reached_depth as usize;
+            o.visited_count += solution.visited_count;
+            o.processed_count += solution.processed_count;
+            o.duration += solution.duration;
         }
-    }
 
-    for (i, &v) in counter.iter().enumerate() {
-        if let Some(o) = temp.get_mut(&((i + 1) as u8)) {
-            o.result_len /= v as usize;
-            o.reached_depth /= v as usize;
-            o.visited_count /= v as usize;
-            o.processed_count /= v as usize;
-            o.duration /= v as f64;
-        }
+        let count = problems.len();
+        o.result_len /= count;
+        o.reached_depth /= count;
+        o.visited_count /= count;
+        o.processed_count /= count;
+        o.duration /= count as f64;
     }
 
-    Ok(temp)
+    temp
 }